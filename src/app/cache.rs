@@ -1,28 +1,102 @@
-use std::io::ErrorKind;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs::{File, OpenOptions},
+    io::{self, ErrorKind},
+    os::fd::AsRawFd,
+};
 
 use anyhow::Context;
 use serde::{Deserialize, Serialize};
 use tokio::fs;
 
 use crate::{
-    app::{ActiveWindow, App, MainWindow, side_bar::port_forwards::PortForward},
+    app::{
+        ActiveWindow, App, MainWindow,
+        common::{PortChoice, PortField},
+        side_bar::port_forwards::{PortForward, PortForwardProfile},
+    },
     error::{AppError, AppResult},
-    files::{CACHE_PATH, ensure_app_dir},
-    kubectl::pods::{Pod, PodContainer},
+    files::{CACHE_LOCK_PATH, CACHE_PATH, ensure_app_dir},
+    kubectl::{TargetKind, context, pods::Pod},
 };
 
-pub async fn save_cache(app: &App) -> AppResult<()> {
+/// Lock on the saved state. The UI holds it shared for as long as it runs, as it writes the whole
+/// state back on quit, and `pro-kube pf` holds it exclusively while changing the state. Released
+/// when dropped.
+#[derive(Debug)]
+pub struct CacheLock {
+    _file: File,
+}
+
+impl CacheLock {
+    /// Waits for a running `pro-kube pf` to finish, other UIs do not block.
+    pub async fn shared() -> AppResult<Self> {
+        let file = open_lock_file().await?;
+
+        tokio::task::spawn_blocking(move || lock_file(file, libc::LOCK_SH))
+            .await
+            .context("failed to wait for the cache lock")
+            .map_err(AppError::CacheError)?
+            .context("failed to lock the cache")
+            .map_err(AppError::CacheError)
+    }
+
+    /// Fails while a UI is running, its state would overwrite the changes on quit.
+    pub async fn exclusive() -> AppResult<Self> {
+        let file = open_lock_file().await?;
+
+        lock_file(file, libc::LOCK_EX | libc::LOCK_NB).map_err(|err| {
+            let err = match err.kind() {
+                ErrorKind::WouldBlock => anyhow::anyhow!(
+                    "the UI is running, manage the port forwards from it or quit it first"
+                ),
+                _ => anyhow::Error::new(err).context("failed to lock the cache"),
+            };
+
+            AppError::CacheError(err)
+        })
+    }
+}
+
+async fn open_lock_file() -> AppResult<File> {
     ensure_app_dir().await?;
 
+    OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(CACHE_LOCK_PATH)
+        .with_context(|| format!("failed to open {CACHE_LOCK_PATH}"))
+        .map_err(AppError::CacheError)
+}
+
+fn lock_file(file: File, operation: libc::c_int) -> io::Result<CacheLock> {
+    if unsafe { libc::flock(file.as_raw_fd(), operation) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(CacheLock { _file: file })
+}
+
+pub async fn save_cache(app: &App) -> AppResult<()> {
+    let mut contexts = app.context_caches.clone();
+    contexts.insert(app.context.clone(), app.context_cache());
+
     let cache_payload = AppCache {
-        namespaces: app.namespaces.clone().into(),
-        pods: app.pods.clone().map(|p| p.into()),
+        context: app.context.clone(),
+        contexts,
         active_window: app.active_window,
         main_window: app.main_window,
-        side_bar: app.side_bar.clone().into(),
+        restore_port_forwards: app.restore_port_forwards,
     };
 
-    let json = serde_json::to_string(&cache_payload)
+    write_cache(&cache_payload).await
+}
+
+pub async fn write_cache(cache_payload: &AppCache) -> AppResult<()> {
+    ensure_app_dir().await?;
+
+    let json = serde_json::to_string(cache_payload)
         .context("failed to serialize cache")
         .map_err(AppError::CacheError)?;
 
@@ -34,54 +108,111 @@ pub async fn save_cache(app: &App) -> AppResult<()> {
     Ok(())
 }
 
-pub async fn read_cache() -> Option<AppCache> {
+pub async fn read_cache() -> AppResult<Option<AppCache>> {
     let content = match fs::read(CACHE_PATH).await {
         Ok(content) => content,
         Err(err) => {
             if err.kind() == ErrorKind::NotFound {
-                return None;
+                return Ok(None);
             }
 
-            // TODO: handle error: show warning or something
-            todo!()
-            // return Err(AppError::CacheError(anyhow::format_err!(
-            //     "failed to read cache into string: {:?}",
-            //     err
-            // )));
+            return Err(AppError::CacheError(anyhow::format_err!(
+                "failed to read cache into string: {:?}",
+                err
+            )));
         }
     };
 
-    let cache: Option<AppCache> = serde_json::from_slice(&content).ok();
-    cache
+    let err = match serde_json::from_slice(&content) {
+        Ok(cache) => return Ok(Some(cache)),
+        Err(err) => err,
+    };
+
+    // Saved before the state was kept per kubeconfig context, it belongs to the current one.
+    if let Ok(legacy) = serde_json::from_slice::<LegacyAppCache>(&content) {
+        let context = context::get_current_context().await?;
+        return Ok(Some(legacy.into_cache(context)));
+    }
+
+    Err(AppError::CacheError(anyhow::format_err!(
+        "failed to parse the saved state in {CACHE_PATH}, it is replaced on quit: {err}"
+    )))
+}
+
+/// The state saved before kubeconfig contexts. Only what still has the same shape is migrated,
+/// the app opens on the namespaces and loads the pods again.
+#[derive(Debug, Deserialize)]
+struct LegacyAppCache {
+    namespaces: NamespacesListCache,
+    side_bar: SideBarCache,
+}
+
+impl LegacyAppCache {
+    fn into_cache(self, context: String) -> AppCache {
+        let mut side_bar = self.side_bar;
+
+        for item in &mut side_bar.port_forwards.list {
+            item.context = context.clone();
+        }
+
+        let context_cache = ContextCache {
+            namespaces: self.namespaces,
+            pods: None,
+            side_bar,
+        };
+
+        AppCache {
+            contexts: HashMap::from([(context.clone(), context_cache)]),
+            context,
+            main_window: MainWindow::Namespaces,
+            active_window: ActiveWindow::Main(MainWindow::Namespaces),
+            restore_port_forwards: false,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AppCache {
+    pub context: String,
+    pub contexts: HashMap<String, ContextCache>,
+    pub main_window: MainWindow,
+    pub active_window: ActiveWindow,
+    /// Relaunch the forwards that were active when the app last exited.
+    #[serde(default)]
+    pub restore_port_forwards: bool,
+}
+
+/// Everything that belongs to a single kubeconfig context, so one cluster's history
+/// does not leak into another's.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextCache {
     pub namespaces: NamespacesListCache,
     pub pods: Option<PodsListCache>,
     pub side_bar: SideBarCache,
-    pub main_window: MainWindow,
-    pub active_window: ActiveWindow,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SideBarCache {
     pub recent_namespaces: RecentNamespacesListCache,
     pub port_forwards: PortForwardsListCache,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PortForwardsListCache {
     pub list: Vec<PortForward>,
     pub state: StateCache,
+    #[serde(default)]
+    pub profiles: Vec<PortForwardProfile>,
+    #[serde(default)]
+    pub profiles_state: StateCache,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecentNamespacesListCache {
     pub recent_namespaces_list: FilterableListCache<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PodsListCache {
     pub original_list: Vec<Pod>,
     pub filtered_list: Vec<Pod>,
@@ -91,26 +222,31 @@ pub struct PodsListCache {
     pub longest_name: u16,
     pub port_forward_popup: Option<PortForwardPopupCache>,
     pub namespace: String,
+    pub context: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PortForwardPopupCache {
-    pub port: String,
-    pub pod_containers: FilterableListCache<PodContainer>,
-    pub selected_container: Option<PodContainer>,
+    pub target_kind: TargetKind,
+    pub target_name: String,
+    pub choices: Vec<PortChoice>,
+    pub state: StateCache,
+    pub marked: BTreeSet<usize>,
+    pub fields: Option<Vec<PortField>>,
+    pub focus: usize,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NamespacesListCache {
     pub namespace_list: FilterableListCache<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct StateCache {
     pub selected: Option<usize>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FilterableListCache<T> {
     pub list: Vec<T>,
     pub state: StateCache,
@@ -120,3 +256,55 @@ pub struct FilterableListCache<T> {
     pub filter: String,
     pub is_filter_mod: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrates_the_single_context_layout() {
+        let list = r#"{
+            "list": ["default", "web"],
+            "state": { "selected": 1 },
+            "list_name": "Namespaces",
+            "is_filterable": true,
+            "filtered_list": [0, 1],
+            "filter": "",
+            "is_filter_mod": false
+        }"#;
+        let content = format!(
+            r#"{{
+                "namespaces": {{ "namespace_list": {list} }},
+                "pods": null,
+                "side_bar": {{
+                    "recent_namespaces": {{ "recent_namespaces_list": {list} }},
+                    "port_forwards": {{
+                        "list": [{{
+                            "namespace": "web",
+                            "pod_name": "api-0",
+                            "local_port": 8080,
+                            "app_port": 80,
+                            "pid": null
+                        }}],
+                        "state": {{ "selected": 0 }}
+                    }}
+                }},
+                "main_window": "Pods",
+                "active_window": {{ "Main": "Pods" }}
+            }}"#
+        );
+
+        assert!(serde_json::from_str::<AppCache>(&content).is_err());
+
+        let legacy: LegacyAppCache = serde_json::from_str(&content).unwrap();
+        let cache = legacy.into_cache("prod".to_string());
+
+        assert_eq!(cache.context, "prod");
+        assert_eq!(cache.main_window, MainWindow::Namespaces);
+
+        let port_forwards = &cache.contexts["prod"].side_bar.port_forwards.list;
+        assert_eq!(port_forwards.len(), 1);
+        assert_eq!(port_forwards[0].context, "prod");
+        assert_eq!(port_forwards[0].target_name, "api-0");
+    }
+}
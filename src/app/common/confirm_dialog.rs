@@ -0,0 +1,209 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+};
+
+use crate::app::common::{build_block, centered_rect};
+
+/// An option of the action being confirmed, changed with its key.
+#[derive(Debug, Clone)]
+pub struct ConfirmOption {
+    pub key: char,
+    pub label: String,
+    pub value: OptionValue,
+}
+
+#[derive(Debug, Clone)]
+pub enum OptionValue {
+    Flag(bool),
+    /// Typed in after pressing the option's key. `None` leaves the choice to kubectl.
+    Number(Option<u32>),
+}
+
+pub enum ConfirmAction {
+    Confirmed,
+    Cancelled,
+}
+
+/// Asks before a destructive action, listing exactly what it will be applied to.
+#[derive(Debug, Clone)]
+pub struct ConfirmDialog {
+    title: String,
+    items: Vec<String>,
+    options: Vec<ConfirmOption>,
+    /// Index of the number option being typed in.
+    editing: Option<usize>,
+}
+
+impl ConfirmDialog {
+    pub fn new(title: String, items: Vec<String>) -> Self {
+        Self {
+            title,
+            items,
+            options: vec![],
+            editing: None,
+        }
+    }
+
+    pub fn with_option(mut self, key: char, label: &str, value: OptionValue) -> Self {
+        self.options.push(ConfirmOption {
+            key,
+            label: label.to_string(),
+            value,
+        });
+
+        self
+    }
+
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    pub fn flag(&self, key: char) -> bool {
+        self.options
+            .iter()
+            .any(|option| option.key == key && matches!(option.value, OptionValue::Flag(true)))
+    }
+
+    pub fn number(&self, key: char) -> Option<u32> {
+        self.options
+            .iter()
+            .find(|option| option.key == key)
+            .and_then(|option| match option.value {
+                OptionValue::Number(number) => number,
+                OptionValue::Flag(_) => None,
+            })
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let mut lines: Vec<Line> = vec![];
+
+        if !self.options.is_empty() {
+            lines.push(Line::default());
+        }
+
+        for (index, option) in self.options.iter().enumerate() {
+            let value = match option.value {
+                OptionValue::Flag(true) => "on".to_string(),
+                OptionValue::Flag(false) => "off".to_string(),
+                OptionValue::Number(Some(number)) => number.to_string(),
+                OptionValue::Number(None) if self.editing == Some(index) => String::new(),
+                OptionValue::Number(None) => "default".to_string(),
+            };
+
+            let value_style = if self.editing == Some(index) {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Yellow)
+            };
+
+            lines.push(Line::from(vec![
+                Span::raw(format!(" [{}] {}: ", option.key, option.label)),
+                Span::styled(value, value_style),
+            ]));
+        }
+
+        lines.push(Line::default());
+        lines.push(Line::styled(
+            match self.editing {
+                Some(_) => " Type a number | Enter done",
+                None => " y/Enter confirm | n/Esc cancel",
+            },
+            Style::default().fg(Color::DarkGray),
+        ));
+
+        let area = frame.area();
+
+        // Every item is listed as long as the screen has room, borders take two lines.
+        let room = (area.height as usize).saturating_sub(lines.len() + 2);
+        let listed = match self.items.len() > room {
+            true => room.saturating_sub(1),
+            false => self.items.len(),
+        };
+
+        let mut items: Vec<Line> = self
+            .items
+            .iter()
+            .take(listed)
+            .map(|item| Line::from(format!(" • {item}")))
+            .collect();
+
+        if listed < self.items.len() {
+            items.push(Line::from(format!(
+                "   ... and {} more",
+                self.items.len() - listed
+            )));
+        }
+
+        lines.splice(0..0, items);
+
+        let width = lines
+            .iter()
+            .map(|line| line.width() as u16 + 4)
+            .chain([self.title.len() as u16 + 4, 40])
+            .max()
+            .unwrap_or(40)
+            .min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+
+        let area = centered_rect(area, width, height);
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(lines).block(build_block(self.title.as_str(), true)),
+            area,
+        );
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) -> Option<ConfirmAction> {
+        if let Some(index) = self.editing {
+            if let OptionValue::Number(number) = &mut self.options[index].value {
+                match key.code {
+                    KeyCode::Char(ch) if ch.is_ascii_digit() => {
+                        let digit = ch.to_digit(10).unwrap_or(0);
+                        *number = number
+                            .unwrap_or(0)
+                            .checked_mul(10)
+                            .and_then(|number| number.checked_add(digit))
+                            .or(*number);
+                    }
+                    // A single digit is emptied out, leaving the choice to kubectl again.
+                    KeyCode::Backspace => {
+                        *number = number
+                            .filter(|number| *number >= 10)
+                            .map(|number| number / 10)
+                    }
+                    KeyCode::Enter | KeyCode::Esc | KeyCode::Tab => self.editing = None,
+                    _ => {}
+                }
+            }
+
+            return None;
+        }
+
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => return Some(ConfirmAction::Confirmed),
+            KeyCode::Char('n') | KeyCode::Esc => return Some(ConfirmAction::Cancelled),
+            KeyCode::Char(ch) => {
+                let index = self.options.iter().position(|option| option.key == ch)?;
+
+                match &mut self.options[index].value {
+                    OptionValue::Flag(value) => *value = !*value,
+                    OptionValue::Number(number) => {
+                        *number = None;
+                        self.editing = Some(index);
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        None
+    }
+}
@@ -7,7 +7,7 @@ use ratatui::{
 
 use crate::app::{
     cache::{FilterableListCache, StateCache},
-    common::{build_block, get_highlight_style},
+    common::{build_block, get_highlight_style, loading_title},
 };
 
 #[derive(Default, Debug, Clone)]
@@ -19,6 +19,7 @@ pub struct FilterableList<T> {
     filtered_list: Vec<usize>,
     filter: String,
     is_filter_mod: bool,
+    pub is_loading: bool,
 }
 
 impl<T> From<FilterableList<T>> for FilterableListCache<T> {
@@ -50,6 +51,7 @@ impl<T> From<FilterableListCache<T>> for FilterableList<T> {
             state,
             is_filterable: value.is_filterable,
             list_name: value.list_name,
+            is_loading: false,
         }
     }
 }
@@ -75,9 +77,15 @@ where
             is_filterable,
             list_name,
             state,
+            is_loading: false,
         }
     }
 
+    /// Keys are typed into the filter, they are not shortcuts.
+    pub fn is_filtering(&self) -> bool {
+        self.is_filter_mod
+    }
+
     pub fn set_items(&mut self, new_list: Vec<Item>) {
         self.filtered_list = new_list
             .iter()
@@ -96,7 +104,8 @@ where
             .map(|index| ListItem::new(self.list[*index].as_ref()))
             .collect();
 
-        let block = build_block(self.list_name.as_str(), !self.is_filterable && is_focused);
+        let title = loading_title(self.list_name.as_str(), self.is_loading);
+        let block = build_block(title.as_str(), !self.is_filterable && is_focused);
 
         let list = List::new(namespaces_list_items)
             .block(block)
@@ -8,6 +8,7 @@ use crate::app::{
 pub fn handle_general_keys(key: KeyEvent, event_sender: &EventSender) -> bool {
     match key.code {
         KeyCode::Char('q') => event_sender.send(AppEvent::Quit),
+        KeyCode::Char('n') => event_sender.send(AppEvent::ToggleNotificationsHistory),
         KeyCode::Char('1') => event_sender.send(AppEvent::Focus(ActiveWindow::SideBar(
             SideBarWindow::RecentNamespaces,
         ))),
@@ -17,6 +18,12 @@ pub fn handle_general_keys(key: KeyEvent, event_sender: &EventSender) -> bool {
         KeyCode::Char('3') => {
             event_sender.send(AppEvent::Focus(ActiveWindow::Main(MainWindow::Namespaces)))
         }
+        KeyCode::Char('4') => {
+            event_sender.send(AppEvent::Focus(ActiveWindow::Main(MainWindow::Contexts)))
+        }
+        KeyCode::Char('5') => event_sender.send(AppEvent::Focus(ActiveWindow::SideBar(
+            SideBarWindow::PortForwardProfiles,
+        ))),
 
         _ => return false,
     };
@@ -1,13 +1,21 @@
+mod confirm_dialog;
 mod filterable_list;
 mod general_key_handler;
+mod port_forward_popup;
 
+pub use confirm_dialog::*;
 pub use filterable_list::*;
 pub use general_key_handler::*;
+pub use port_forward_popup::*;
+
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, BorderType, Borders},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
 };
 
 pub fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
@@ -30,8 +38,38 @@ pub fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+/// Single line input popup in the middle of the screen.
+pub fn draw_text_input(frame: &mut Frame, title: &str, text: &str) {
+    let width = (title.len() as u16 + 6).max(44).min(frame.area().width);
+    let area = centered_rect(frame.area(), width, 3);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(text).block(build_block(title, true)), area);
+}
+
 pub const FOCUS_COLOR: Color = Color::Cyan;
 
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// Current frame of the loading spinner, advanced by wall clock so every panel spins in sync.
+pub fn spinner_frame() -> char {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+
+    SPINNER_FRAMES[(millis / 100) as usize % SPINNER_FRAMES.len()]
+}
+
+/// Appends the spinner to a panel title while its data is being loaded.
+pub fn loading_title(title: &str, is_loading: bool) -> String {
+    if is_loading {
+        format!("{title} {}", spinner_frame())
+    } else {
+        title.to_string()
+    }
+}
+
 pub fn build_block(title: &'_ str, is_focused: bool) -> Block<'_> {
     let mut block = Block::default()
         .title(title)
@@ -51,3 +89,31 @@ pub fn get_highlight_style() -> Style {
         .fg(Color::Black)
         .add_modifier(Modifier::BOLD)
 }
+
+/// Marks every occurrence of `query` in `line`.
+pub fn highlight_matches<'a>(line: &'a str, query: &str) -> Line<'a> {
+    if query.is_empty() {
+        return Line::from(line);
+    }
+
+    let match_style = Style::default()
+        .fg(Color::Black)
+        .bg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+
+    let mut spans = vec![];
+    let mut rest = line;
+
+    while let Some(position) = rest.find(query) {
+        spans.push(Span::from(&rest[..position]));
+        spans.push(Span::styled(
+            &rest[position..position + query.len()],
+            match_style,
+        ));
+        rest = &rest[position + query.len()..];
+    }
+
+    spans.push(Span::from(rest));
+
+    Line::from(spans)
+}
@@ -0,0 +1,607 @@
+use std::collections::{BTreeSet, HashMap};
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Alignment,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Clear, List, ListItem, ListState, Paragraph},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::{
+        cache::{PortForwardPopupCache, StateCache},
+        common::{build_block, centered_rect, get_highlight_style},
+    },
+    kubectl::{
+        PortMapping, TargetKind, find_free_local_port, is_local_port_free, pods::PodContainer,
+        services::ServicePort,
+    },
+};
+
+/// Local ports held by our own forwards, with a description of the forward holding each.
+pub type ForwardedPorts = HashMap<u16, String>;
+
+/// A remote port offered by the popup: one of a container's or a service's ports.
+/// `port` is `None` for containers without declared ports, the user types it in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortChoice {
+    pub label: String,
+    pub port: Option<u16>,
+}
+
+impl PortChoice {
+    /// One choice per declared port. The container name is only added when the pod has several.
+    pub fn from_containers(containers: &[PodContainer]) -> Vec<PortChoice> {
+        let with_container = |container: &PodContainer, label: String| {
+            if containers.len() > 1 {
+                format!("{}: {label}", container.name)
+            } else {
+                label
+            }
+        };
+
+        containers
+            .iter()
+            .flat_map(|container| {
+                if container.ports.is_empty() {
+                    return vec![PortChoice {
+                        label: with_container(container, "no declared ports".to_string()),
+                        port: None,
+                    }];
+                }
+
+                container
+                    .ports
+                    .iter()
+                    .map(|port| PortChoice {
+                        label: with_container(container, port.label()),
+                        port: Some(port.container_port),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl From<ServicePort> for PortChoice {
+    fn from(value: ServicePort) -> Self {
+        Self {
+            label: value.label(),
+            port: Some(value.port),
+        }
+    }
+}
+
+/// Local and remote port inputs for one of the selected choices.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortField {
+    label: String,
+    local_port: String,
+    remote_port: String,
+    is_remote_editable: bool,
+}
+
+/// A local port that is already bound. `owner` is set when one of our forwards holds it.
+#[derive(Debug, Clone)]
+struct PortConflict {
+    field: usize,
+    port: u16,
+    owner: Option<String>,
+    suggestion: Option<u16>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Input {
+    Local(usize),
+    Remote(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct PortForwardPopup {
+    pub target_kind: TargetKind,
+    pub target_name: String,
+    choices: Vec<PortChoice>,
+    state: ListState,
+    marked: BTreeSet<usize>,
+    fields: Option<Vec<PortField>>,
+    focus: usize,
+    error: Option<String>,
+    conflict: Option<PortConflict>,
+}
+
+impl From<PortForwardPopup> for PortForwardPopupCache {
+    fn from(value: PortForwardPopup) -> Self {
+        Self {
+            target_kind: value.target_kind,
+            target_name: value.target_name,
+            choices: value.choices,
+            state: StateCache {
+                selected: value.state.selected(),
+            },
+            marked: value.marked,
+            fields: value.fields,
+            focus: value.focus,
+        }
+    }
+}
+
+impl From<PortForwardPopupCache> for PortForwardPopup {
+    fn from(value: PortForwardPopupCache) -> Self {
+        let mut state = ListState::default();
+        state.select(value.state.selected);
+
+        Self {
+            target_kind: value.target_kind,
+            target_name: value.target_name,
+            choices: value.choices,
+            state,
+            marked: value.marked,
+            fields: value.fields,
+            focus: value.focus,
+            error: None,
+            conflict: None,
+        }
+    }
+}
+
+pub enum PortForwardPopupAction {
+    /// `replace` is set when the user chose to stop our own forwards holding these local ports.
+    PortForward {
+        ports: Vec<PortMapping>,
+        replace: bool,
+    },
+    Quit,
+}
+
+impl PortForwardPopup {
+    const ALLOWED_CHARS: [char; 10] = ['1', '2', '3', '4', '5', '6', '7', '8', '9', '0'];
+    const MAX_PORT_LENGTH: usize = 5;
+
+    pub fn new(target_kind: TargetKind, target_name: String, choices: Vec<PortChoice>) -> Self {
+        let mut state = ListState::default();
+        state.select(Some(0));
+
+        let mut popup = Self {
+            target_kind,
+            target_name,
+            choices,
+            state,
+            marked: BTreeSet::new(),
+            fields: None,
+            focus: 0,
+            error: None,
+            conflict: None,
+        };
+
+        // Nothing to pick from, go straight to the port inputs.
+        if popup.choices.len() == 1 {
+            popup.open_fields(vec![0]);
+        }
+
+        popup
+    }
+
+    fn open_fields(&mut self, indexes: Vec<usize>) {
+        let fields = indexes
+            .into_iter()
+            .filter_map(|index| self.choices.get(index))
+            .map(|choice| {
+                let port = choice.port.map(|port| port.to_string()).unwrap_or_default();
+
+                PortField {
+                    label: choice.label.clone(),
+                    local_port: port.clone(),
+                    remote_port: port,
+                    is_remote_editable: choice.port.is_none(),
+                }
+            })
+            .collect();
+
+        self.fields = Some(fields);
+        self.focus = 0;
+        self.error = None;
+        self.conflict = None;
+    }
+
+    /// Inputs in tab order. A remote port is only asked for when the container declares none.
+    fn inputs(&self) -> Vec<Input> {
+        let Some(fields) = &self.fields else {
+            return vec![];
+        };
+
+        fields
+            .iter()
+            .enumerate()
+            .flat_map(|(index, field)| {
+                let mut inputs = vec![];
+
+                if field.is_remote_editable {
+                    inputs.push(Input::Remote(index));
+                }
+
+                inputs.push(Input::Local(index));
+                inputs
+            })
+            .collect()
+    }
+
+    fn focused_input(&self) -> Option<Input> {
+        self.inputs().get(self.focus).copied()
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let title = format!(
+            "Forward to {}",
+            self.target_kind.target(self.target_name.as_str())
+        );
+
+        if self.fields.is_some() {
+            return self.draw_fields(frame, title.as_str());
+        }
+
+        let width = self
+            .choices
+            .iter()
+            .map(|choice| choice.label.len() as u16 + 8)
+            .chain([title.len() as u16 + 4, 40])
+            .max()
+            .unwrap_or(40)
+            .min(frame.area().width);
+
+        let area = centered_rect(frame.area(), width, self.choices.len() as u16 + 3);
+
+        let items: Vec<ListItem> = self
+            .choices
+            .iter()
+            .enumerate()
+            .map(|(index, choice)| {
+                let mark = if self.marked.contains(&index) {
+                    "[x]"
+                } else {
+                    "[ ]"
+                };
+
+                ListItem::new(format!("{mark} {}", choice.label))
+            })
+            .collect();
+
+        let block = build_block(title.as_str(), true)
+            .title_alignment(Alignment::Center)
+            .title_bottom(Line::from(" space mark | Enter select ").centered());
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(get_highlight_style());
+
+        frame.render_widget(Clear, area);
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+
+    fn draw_fields(&self, frame: &mut Frame, title: &str) {
+        let Some(fields) = &self.fields else {
+            return;
+        };
+
+        let focused_input = self.focused_input();
+        let input_style = |input: Input| {
+            if Some(input) == focused_input {
+                get_highlight_style()
+            } else {
+                Style::default().fg(Color::Cyan)
+            }
+        };
+
+        let mut lines: Vec<Line> = fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let remote_port = if field.is_remote_editable {
+                    Span::styled(
+                        format!("[{:<5}]", field.remote_port),
+                        input_style(Input::Remote(index)),
+                    )
+                } else {
+                    Span::raw(format!(" {:<5} ", field.remote_port))
+                };
+
+                Line::from(vec![
+                    Span::raw(format!("{}  remote ", field.label)),
+                    remote_port,
+                    Span::raw(" local "),
+                    Span::styled(
+                        format!("[{:<5}]", field.local_port),
+                        input_style(Input::Local(index)),
+                    ),
+                ])
+            })
+            .collect();
+
+        if let Some(error) = &self.error {
+            lines.push(Line::styled(
+                error.as_str(),
+                Style::default().fg(Color::Red),
+            ));
+        }
+
+        if let Some(conflict) = &self.conflict {
+            let owner = match &conflict.owner {
+                Some(owner) => format!("Port {} is used by {owner}", conflict.port),
+                None => format!("Port {} is already in use", conflict.port),
+            };
+
+            let suggestion = match conflict.suggestion {
+                Some(port) => format!(", changed to {port}"),
+                None => ", no free port found".to_string(),
+            };
+
+            lines.push(Line::styled(
+                format!("{owner}{suggestion}"),
+                Style::default().fg(Color::Yellow),
+            ));
+
+            if conflict.owner.is_some() {
+                lines.push(Line::styled(
+                    "Press r to stop that forward and use the port anyway",
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+        }
+
+        let width = lines
+            .iter()
+            .map(|line| line.width() as u16 + 4)
+            .chain([title.len() as u16 + 4, 30])
+            .max()
+            .unwrap_or(30)
+            .min(frame.area().width);
+
+        let area = centered_rect(frame.area(), width, lines.len() as u16 + 2);
+
+        let block = build_block(title, true)
+            .title_alignment(Alignment::Center)
+            .title_bottom(Line::from(" Tab next | Enter forward ").centered());
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    /// Parses every input. Errors name the offending port so the user knows what to fix.
+    fn port_mappings(&self) -> Result<Vec<PortMapping>, String> {
+        let fields = self.fields.as_deref().unwrap_or_default();
+
+        if fields.is_empty() {
+            return Err("Select at least one port".to_string());
+        }
+
+        let mut mappings: Vec<PortMapping> = vec![];
+
+        for field in fields {
+            let parse = |value: &str, side: &str| match value.parse::<u16>() {
+                Ok(port) if port > 0 => Ok(port),
+                _ => Err(format!("Invalid {side} port for {}", field.label)),
+            };
+
+            let mapping = PortMapping::new(
+                parse(field.local_port.as_str(), "local")?,
+                parse(field.remote_port.as_str(), "remote")?,
+            );
+
+            if mappings
+                .iter()
+                .any(|other| other.local_port == mapping.local_port)
+            {
+                return Err(format!(
+                    "Local port {} is used more than once",
+                    mapping.local_port
+                ));
+            }
+
+            mappings.push(mapping);
+        }
+
+        Ok(mappings)
+    }
+
+    /// Finds the first local port that is bound already. Ports held by our own forwards are
+    /// skipped when they are going to be replaced.
+    fn find_conflict(
+        &self,
+        ports: &[PortMapping],
+        forwarded_ports: &ForwardedPorts,
+        replace: bool,
+    ) -> Option<PortConflict> {
+        let (field, port, owner) = ports.iter().enumerate().find_map(|(field, ports)| {
+            let port = ports.local_port;
+
+            match forwarded_ports.get(&port) {
+                Some(owner) if !replace => Some((field, port, Some(owner.clone()))),
+                Some(_) => None,
+                None if !is_local_port_free(port) => Some((field, port, None)),
+                None => None,
+            }
+        })?;
+
+        let suggestion = find_free_local_port(port, |candidate| {
+            forwarded_ports.contains_key(&candidate)
+                || ports.iter().any(|ports| ports.local_port == candidate)
+        });
+
+        Some(PortConflict {
+            field,
+            port,
+            owner,
+            suggestion,
+        })
+    }
+
+    fn submit(
+        &mut self,
+        forwarded_ports: &ForwardedPorts,
+        replace: bool,
+    ) -> Option<PortForwardPopupAction> {
+        self.error = None;
+        self.conflict = None;
+
+        let ports = match self.port_mappings() {
+            Ok(ports) => ports,
+            Err(error) => {
+                self.error = Some(error);
+                return None;
+            }
+        };
+
+        let Some(conflict) = self.find_conflict(&ports, forwarded_ports, replace) else {
+            return Some(PortForwardPopupAction::PortForward { ports, replace });
+        };
+
+        // Offer the next free port right away, Enter accepts it.
+        if let Some(suggestion) = conflict.suggestion
+            && let Some(field) = self
+                .fields
+                .as_mut()
+                .and_then(|fields| fields.get_mut(conflict.field))
+        {
+            field.local_port = suggestion.to_string();
+        }
+
+        self.conflict = Some(conflict);
+        None
+    }
+
+    /// Puts the conflicting port back and forwards it, stopping our forward that holds it.
+    fn replace_conflicting(
+        &mut self,
+        forwarded_ports: &ForwardedPorts,
+    ) -> Option<PortForwardPopupAction> {
+        let conflict = self.conflict.take()?;
+
+        if conflict.owner.is_none() {
+            self.conflict = Some(conflict);
+            return None;
+        }
+
+        if let Some(field) = self
+            .fields
+            .as_mut()
+            .and_then(|fields| fields.get_mut(conflict.field))
+        {
+            field.local_port = conflict.port.to_string();
+        }
+
+        self.submit(forwarded_ports, true)
+    }
+
+    pub fn handle_key_event(
+        &mut self,
+        key: KeyEvent,
+        forwarded_ports: &ForwardedPorts,
+    ) -> Option<PortForwardPopupAction> {
+        if self.fields.is_some() {
+            return self.handle_fields_key_event(key, forwarded_ports);
+        }
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => self.select_prev(),
+            KeyCode::Char(' ') => {
+                if let Some(selected) = self.state.selected()
+                    && !self.marked.remove(&selected)
+                {
+                    self.marked.insert(selected);
+                }
+            }
+            KeyCode::Enter => {
+                let indexes = if self.marked.is_empty() {
+                    self.state.selected().into_iter().collect()
+                } else {
+                    self.marked.iter().copied().collect()
+                };
+
+                self.open_fields(indexes);
+            }
+            KeyCode::Esc => return Some(PortForwardPopupAction::Quit),
+            _ => {}
+        };
+
+        None
+    }
+
+    fn handle_fields_key_event(
+        &mut self,
+        key: KeyEvent,
+        forwarded_ports: &ForwardedPorts,
+    ) -> Option<PortForwardPopupAction> {
+        let inputs_count = self.inputs().len();
+        let focused_input = self.focused_input();
+        let fields = self.fields.as_mut()?;
+
+        let value = match focused_input {
+            Some(Input::Local(index)) => Some(&mut fields[index].local_port),
+            Some(Input::Remote(index)) => Some(&mut fields[index].remote_port),
+            None => None,
+        };
+
+        match key.code {
+            KeyCode::Char(ch) if PortForwardPopup::ALLOWED_CHARS.contains(&ch) => {
+                if let Some(value) = value
+                    && value.len() < PortForwardPopup::MAX_PORT_LENGTH
+                {
+                    value.push(ch);
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(value) = value {
+                    value.pop();
+                }
+            }
+            KeyCode::Tab | KeyCode::Down => self.focus = (self.focus + 1) % inputs_count.max(1),
+            KeyCode::BackTab | KeyCode::Up => {
+                self.focus = (self.focus + inputs_count.max(1) - 1) % inputs_count.max(1)
+            }
+            KeyCode::Enter => return self.submit(forwarded_ports, false),
+            KeyCode::Char('r') => return self.replace_conflicting(forwarded_ports),
+            KeyCode::Esc => {
+                // Go back to the list when there was something to choose from.
+                if self.choices.len() == 1 {
+                    return Some(PortForwardPopupAction::Quit);
+                }
+
+                self.fields = None;
+                self.error = None;
+                self.conflict = None;
+            }
+            _ => {}
+        };
+
+        None
+    }
+
+    fn select_next(&mut self) {
+        if self.choices.is_empty() {
+            return;
+        }
+
+        let i = match self.state.selected() {
+            Some(i) if i + 1 < self.choices.len() => i + 1,
+            _ => 0,
+        };
+
+        self.state.select(Some(i));
+    }
+
+    fn select_prev(&mut self) {
+        if self.choices.is_empty() {
+            return;
+        }
+
+        let i = match self.state.selected() {
+            Some(i) if i > 0 => i - 1,
+            _ => self.choices.len() - 1,
+        };
+
+        self.state.select(Some(i));
+    }
+}
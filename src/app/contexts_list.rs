@@ -0,0 +1,55 @@
+use ratatui::{Frame, crossterm::event::KeyEvent, layout::Rect};
+
+use crate::app::{
+    common::{FilterableList, ListEvent, handle_general_keys},
+    events::{AppEvent, EventSender},
+};
+
+#[derive(Debug, Clone)]
+pub struct ContextsList {
+    contexts_list: FilterableList<String>,
+    event_sender: EventSender,
+}
+
+impl ContextsList {
+    pub fn new(event_sender: EventSender) -> Self {
+        Self {
+            event_sender,
+            contexts_list: FilterableList::new("Contexts".to_string(), true),
+        }
+    }
+
+    pub fn set_loading(&mut self, is_loading: bool) {
+        self.contexts_list.is_loading = is_loading;
+    }
+
+    pub fn draw(&mut self, area: Rect, frame: &mut Frame, is_focused: bool) {
+        self.contexts_list.draw(area, frame, is_focused);
+    }
+
+    pub fn update_list(&mut self, new_list: Vec<String>, current_context: &str) {
+        let current_index = new_list.iter().position(|c| c == current_context);
+
+        self.contexts_list.set_items(new_list);
+        self.contexts_list.state.select(current_index.or(Some(0)));
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) {
+        let is_filtering = self.contexts_list.is_filtering();
+
+        if let Some(list_event) = self.contexts_list.handle_key(key) {
+            match list_event {
+                ListEvent::Quit => {
+                    self.event_sender.send(AppEvent::Quit);
+                }
+                ListEvent::SelectedItem(item) => {
+                    self.event_sender.send(AppEvent::SelectContext(item));
+                }
+            };
+        }
+
+        if !is_filtering {
+            handle_general_keys(key, &self.event_sender);
+        }
+    }
+}
@@ -0,0 +1,355 @@
+mod scale_popup;
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    crossterm::event::KeyEvent,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Cell, Paragraph, Row, Table, TableState},
+};
+
+use crate::{
+    app::{
+        common::{build_block, get_highlight_style, handle_general_keys, loading_title},
+        deployments_list::scale_popup::{ScalePopup, ScalePopupAction},
+        events::{AppEvent, EventSender, Log},
+    },
+    error::AppResult,
+    kubectl::{
+        KubectlStream, StreamEvent,
+        deployments::{Deployment, stream_rollout_status},
+        manifest::ResourceRef,
+    },
+};
+
+/// Progress lines kept for the rollout panel, `kubectl rollout status` prints one per step.
+const MAX_ROLLOUT_LINES: usize = 6;
+
+/// Output of `kubectl rollout status` for one deployment, followed live below the list.
+struct Rollout {
+    deployment: String,
+    lines: Vec<Line<'static>>,
+    /// `None` while kubectl runs, then its exit code, which is missing when it was killed.
+    exited: Option<Option<i32>>,
+    _stream: KubectlStream,
+}
+
+/// Deployments of the namespace opened in the pods list.
+pub struct DeploymentsList {
+    context: String,
+    namespace: String,
+    list: Vec<Deployment>,
+    state: TableState,
+    is_loading: bool,
+    scale_popup: Option<ScalePopup>,
+    rollout: Option<Rollout>,
+    rollout_id: u64,
+    event_sender: EventSender,
+}
+
+impl DeploymentsList {
+    pub fn new(context: String, namespace: String, event_sender: EventSender) -> Self {
+        Self {
+            context,
+            namespace,
+            list: vec![],
+            state: TableState::default(),
+            is_loading: true,
+            scale_popup: None,
+            rollout: None,
+            rollout_id: 0,
+            event_sender,
+        }
+    }
+
+    /// Context and namespace of the listed deployments.
+    pub fn target(&self) -> (String, String) {
+        (self.context.clone(), self.namespace.clone())
+    }
+
+    pub fn set_loading(&mut self, is_loading: bool) {
+        self.is_loading = is_loading;
+    }
+
+    pub fn update_list(&mut self, list: Vec<Deployment>) {
+        let selected_name = self.selected_deployment().map(|d| d.name.clone());
+
+        self.list = list;
+
+        let selected = match selected_name {
+            _ if self.list.is_empty() => None,
+            Some(name) => self
+                .list
+                .iter()
+                .position(|d| d.name == name)
+                .or(self.state.selected().map(|i| i.min(self.list.len() - 1))),
+            None => Some(0),
+        };
+
+        self.state.select(selected);
+    }
+
+    /// Starts following the rollout of `deployment`, replacing the one followed so far.
+    pub fn watch_rollout(&mut self, deployment: String) -> AppResult<()> {
+        self.rollout_id += 1;
+
+        let event_sender = self.event_sender.clone();
+        let rollout_id = self.rollout_id;
+
+        let stream = stream_rollout_status(
+            self.context.as_str(),
+            self.namespace.as_str(),
+            deployment.as_str(),
+            move |event| event_sender.send(AppEvent::RolloutStatus { rollout_id, event }),
+        )?;
+
+        self.rollout = Some(Rollout {
+            deployment,
+            lines: vec![],
+            exited: None,
+            _stream: stream,
+        });
+
+        Ok(())
+    }
+
+    /// Returns `false` for output of a rollout that is no longer followed.
+    pub fn handle_rollout_event(&mut self, rollout_id: u64, event: StreamEvent) -> bool {
+        let Some(rollout) = &mut self.rollout else {
+            return false;
+        };
+
+        if rollout_id != self.rollout_id {
+            return false;
+        }
+
+        match event {
+            StreamEvent::Stdout(line) => rollout.lines.push(Line::from(line)),
+            StreamEvent::Stderr(line) => rollout
+                .lines
+                .push(Line::styled(line, Style::default().fg(Color::Red))),
+            StreamEvent::Exited(code) => rollout.exited = Some(code),
+        }
+
+        let skip = rollout.lines.len().saturating_sub(MAX_ROLLOUT_LINES);
+        rollout.lines.drain(..skip);
+
+        true
+    }
+
+    pub fn draw(&mut self, area: Rect, frame: &mut Frame, is_focused: bool) {
+        let table_area = match &self.rollout {
+            Some(rollout) => {
+                let layouts = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints(vec![
+                        Constraint::Min(3),
+                        Constraint::Length(MAX_ROLLOUT_LINES as u16 + 2),
+                    ])
+                    .split(area);
+
+                draw_rollout(rollout, layouts[1], frame);
+                layouts[0]
+            }
+            None => area,
+        };
+
+        self.draw_table(table_area, frame, is_focused);
+
+        if let Some(scale_popup) = &self.scale_popup {
+            scale_popup.draw(frame);
+        }
+    }
+
+    fn draw_table(&mut self, area: Rect, frame: &mut Frame, is_focused: bool) {
+        let header = ["Name", "Ready", "Up-to-date", "Available", "Images"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>();
+
+        let longest_name = self
+            .list
+            .iter()
+            .map(|deployment| deployment.name.len())
+            .max()
+            .unwrap_or(10) as u16;
+
+        let rows: Vec<Row> = self
+            .list
+            .iter()
+            .map(|deployment| {
+                let ready_style = if deployment.ready == deployment.desired {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::Yellow)
+                };
+
+                Row::new([
+                    Cell::from(deployment.name.as_str()),
+                    Cell::from(format!("{}/{}", deployment.ready, deployment.desired))
+                        .style(ready_style),
+                    Cell::from(deployment.up_to_date.to_string()),
+                    Cell::from(deployment.available.to_string()),
+                    Cell::from(deployment.images.join(", ")),
+                ])
+            })
+            .collect();
+
+        let title = loading_title(
+            format!("Deployments in {}", self.namespace).as_str(),
+            self.is_loading,
+        );
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(longest_name + 3),
+                Constraint::Length(9),
+                Constraint::Length(12),
+                Constraint::Length(11),
+                Constraint::Min(5),
+            ],
+        )
+        .header(header)
+        .block(build_block(title.as_str(), is_focused))
+        .row_highlight_style(get_highlight_style());
+
+        frame.render_stateful_widget(table, area, &mut self.state);
+    }
+
+    fn selected_deployment(&self) -> Option<&Deployment> {
+        self.list.get(self.state.selected()?)
+    }
+
+    fn selected_resource(&self) -> Option<ResourceRef> {
+        self.selected_deployment().map(|deployment| ResourceRef {
+            context: self.context.clone(),
+            namespace: self.namespace.clone(),
+            kind: "deployment".to_string(),
+            name: deployment.name.clone(),
+        })
+    }
+
+    fn open_manifest(&self) {
+        if let Some(resource) = self.selected_resource() {
+            self.event_sender.send(AppEvent::OpenManifest(resource));
+        }
+    }
+
+    fn edit(&self) {
+        if let Some(resource) = self.selected_resource() {
+            self.event_sender.send(AppEvent::EditResource(resource));
+        }
+    }
+
+    fn show_pods(&self) {
+        if let Some(deployment) = self.selected_deployment() {
+            self.event_sender.send(AppEvent::ShowDeploymentPods {
+                name: deployment.name.clone(),
+                selector: deployment.selector.clone(),
+            });
+        }
+    }
+
+    fn open_scale_popup(&mut self) {
+        if let Some(deployment) = self.selected_deployment() {
+            self.scale_popup = Some(ScalePopup::new(deployment.name.clone(), deployment.desired));
+        }
+    }
+
+    fn restart(&self) {
+        if let Some(deployment) = self.selected_deployment() {
+            self.event_sender
+                .send(AppEvent::RestartDeployment(deployment.name.clone()));
+        }
+    }
+
+    fn watch_selected_rollout(&mut self) {
+        let Some(deployment) = self.selected_deployment() else {
+            return;
+        };
+
+        if let Err(err) = self.watch_rollout(deployment.name.clone()) {
+            self.event_sender
+                .send(AppEvent::ShowNotification(Log::Error(err.to_string())));
+        }
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) {
+        if let Some(scale_popup) = &mut self.scale_popup {
+            if let Some(action) = scale_popup.handle_key_event(key) {
+                if let ScalePopupAction::Scale(replicas) = action {
+                    self.event_sender.send(AppEvent::ScaleDeployment {
+                        name: scale_popup.deployment.clone(),
+                        replicas,
+                    });
+                }
+
+                self.scale_popup = None;
+            }
+
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => self.select_prev(),
+            KeyCode::Enter => self.show_pods(),
+            KeyCode::Char('s') => self.open_scale_popup(),
+            KeyCode::Char('R') => self.restart(),
+            KeyCode::Char('w') => self.watch_selected_rollout(),
+            KeyCode::Char('r') => self.event_sender.send(AppEvent::ReloadDeployments),
+            KeyCode::Char('y') => self.open_manifest(),
+            KeyCode::Char('e') => self.edit(),
+            KeyCode::Esc if self.rollout.is_some() => self.rollout = None,
+            KeyCode::Esc => self.event_sender.send(AppEvent::CloseDeployments),
+            _ => {}
+        };
+
+        handle_general_keys(key, &self.event_sender);
+    }
+
+    fn select_next(&mut self) {
+        if self.list.is_empty() {
+            return;
+        }
+
+        let i = match self.state.selected() {
+            Some(i) if i + 1 < self.list.len() => i + 1,
+            _ => 0,
+        };
+
+        self.state.select(Some(i));
+    }
+
+    fn select_prev(&mut self) {
+        if self.list.is_empty() {
+            return;
+        }
+
+        let i = match self.state.selected() {
+            Some(i) if i > 0 => i - 1,
+            _ => self.list.len() - 1,
+        };
+
+        self.state.select(Some(i));
+    }
+}
+
+fn draw_rollout(rollout: &Rollout, area: Rect, frame: &mut Frame) {
+    let target = format!("Rollout of deployment/{}", rollout.deployment);
+
+    let title = match rollout.exited {
+        None => loading_title(target.as_str(), true),
+        Some(Some(0)) => format!("{target} (done)"),
+        Some(Some(code)) => format!("{target} (failed with exit code {code})"),
+        Some(None) => format!("{target} (stopped)"),
+    };
+
+    frame.render_widget(
+        Paragraph::new(rollout.lines.clone()).block(build_block(title.as_str(), false)),
+        area,
+    );
+}
@@ -0,0 +1,80 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Alignment,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+};
+
+use crate::app::common::{build_block, centered_rect, get_highlight_style};
+
+#[derive(Debug, Clone)]
+pub struct ScalePopup {
+    pub deployment: String,
+    current: u32,
+    replicas: String,
+}
+
+pub enum ScalePopupAction {
+    Scale(u32),
+    Quit,
+}
+
+impl ScalePopup {
+    const MAX_REPLICAS_LENGTH: usize = 4;
+
+    pub fn new(deployment: String, current: u32) -> Self {
+        Self {
+            deployment,
+            current,
+            // Starts empty, digits appended to the current count would scale to a different one.
+            replicas: String::new(),
+        }
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let title = format!("Scale deployment/{}", self.deployment);
+
+        let width = (title.len() as u16 + 4).max(40).min(frame.area().width);
+        let area = centered_rect(frame.area(), width, 3.min(frame.area().height));
+
+        let line = Line::from(vec![
+            Span::raw("replicas "),
+            Span::styled(format!("[{:<4}]", self.replicas), get_highlight_style()),
+            Span::styled(
+                format!(" currently {}", self.current),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]);
+
+        let block = build_block(title.as_str(), true)
+            .title_alignment(Alignment::Center)
+            .title_bottom(Line::from(" Enter scale | Esc cancel ").centered());
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(Paragraph::new(line).block(block), area);
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) -> Option<ScalePopupAction> {
+        match key.code {
+            KeyCode::Char(ch)
+                if ch.is_ascii_digit() && self.replicas.len() < ScalePopup::MAX_REPLICAS_LENGTH =>
+            {
+                self.replicas.push(ch)
+            }
+            KeyCode::Backspace => {
+                self.replicas.pop();
+            }
+            KeyCode::Enter => {
+                if let Ok(replicas) = self.replicas.parse() {
+                    return Some(ScalePopupAction::Scale(replicas));
+                }
+            }
+            KeyCode::Esc => return Some(ScalePopupAction::Quit),
+            _ => {}
+        };
+
+        None
+    }
+}
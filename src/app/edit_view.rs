@@ -0,0 +1,288 @@
+use std::{
+    fs::Permissions,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::Paragraph,
+};
+use tokio::{
+    fs::{self, OpenOptions},
+    io::AsyncWriteExt,
+    process::Command,
+};
+
+use crate::{
+    app::{
+        MainWindow,
+        common::{build_block, loading_title},
+        events::{AppEvent, EventSender},
+    },
+    error::{AppError, AppResult},
+    files::{EDIT_DIR, ensure_edit_dir},
+    kubectl::manifest::{ManifestFormat, ResourceRef, get_manifest},
+};
+
+/// A resource's manifest being edited in a file of its own, kept until the edit is applied or
+/// discarded so it can be reopened after a failure.
+#[derive(Debug, Clone)]
+pub struct EditSession {
+    pub resource: ResourceRef,
+    pub path: PathBuf,
+    /// Manifest as fetched, to tell whether the editor changed anything.
+    original: String,
+}
+
+/// Manifests may hold secrets, only the user gets to read them. A file left by an earlier edit
+/// is tightened as well, `mode` only applies to new files.
+async fn write_private(path: &Path, content: &str) -> std::io::Result<()> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)
+        .await?;
+
+    file.set_permissions(Permissions::from_mode(0o600)).await?;
+    file.write_all(content.as_bytes()).await?;
+    file.flush().await
+}
+
+impl EditSession {
+    /// Fetches the YAML of `resource` into the edit file.
+    pub async fn start(resource: ResourceRef) -> AppResult<Self> {
+        let original = get_manifest(&resource, ManifestFormat::Yaml).await?;
+
+        ensure_edit_dir().await?;
+
+        // Context names may hold `/` and `:`, e.g. EKS ARNs.
+        let name = format!(
+            "{}_{}_{}_{}",
+            resource.context, resource.namespace, resource.kind, resource.name
+        )
+        .replace(
+            |ch: char| !ch.is_ascii_alphanumeric() && !"-_.".contains(ch),
+            "_",
+        );
+
+        let path = Path::new(EDIT_DIR).join(format!("{name}.yaml"));
+
+        write_private(&path, original.as_str())
+            .await
+            .with_context(|| format!("failed to write {}", path.display()))
+            .map_err(AppError::GeneralError)?;
+
+        Ok(Self {
+            resource,
+            path,
+            original,
+        })
+    }
+
+    /// `$VISUAL` or `$EDITOR`, which may carry arguments like `code --wait`, with `vi` as the
+    /// last resort.
+    pub fn editor_command(&self) -> Command {
+        let editor = std::env::var("VISUAL")
+            .or_else(|_| std::env::var("EDITOR"))
+            .ok()
+            .filter(|editor| !editor.trim().is_empty())
+            .unwrap_or("vi".to_string());
+
+        let mut parts = editor.split_whitespace();
+        let mut command = Command::new(parts.next().unwrap_or("vi"));
+
+        command.args(parts).arg(&self.path);
+        command
+    }
+
+    pub async fn is_changed(&self) -> AppResult<bool> {
+        let edited = fs::read_to_string(&self.path)
+            .await
+            .with_context(|| format!("failed to read {}", self.path.display()))
+            .map_err(AppError::GeneralError)?;
+
+        Ok(edited != self.original)
+    }
+
+    pub fn discard(&self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[derive(Debug, Clone)]
+enum EditState {
+    Diffing,
+    /// Waiting for confirmation. An empty diff means the edit matches the cluster.
+    Diff(Vec<String>),
+    Applying,
+    Failed(String),
+}
+
+/// Preview of an edit as `kubectl diff` shows it, applied once confirmed.
+pub struct EditView {
+    session: EditSession,
+    /// Window to return to on close.
+    opened_from: MainWindow,
+    state: EditState,
+    scroll: usize,
+    page_height: usize,
+    event_sender: EventSender,
+}
+
+impl EditView {
+    pub fn new(session: EditSession, opened_from: MainWindow, event_sender: EventSender) -> Self {
+        Self {
+            session,
+            opened_from,
+            state: EditState::Diffing,
+            scroll: 0,
+            page_height: 0,
+            event_sender,
+        }
+    }
+
+    pub fn session(&self) -> &EditSession {
+        &self.session
+    }
+
+    pub fn opened_from(&self) -> MainWindow {
+        self.opened_from
+    }
+
+    pub fn set_diffing(&mut self) {
+        self.state = EditState::Diffing;
+        self.scroll = 0;
+    }
+
+    pub fn set_applying(&mut self) {
+        self.state = EditState::Applying;
+    }
+
+    pub fn handle_diff(&mut self, result: AppResult<String>) {
+        self.state = match result {
+            Ok(diff) => EditState::Diff(diff.lines().map(String::from).collect()),
+            Err(err) => EditState::Failed(err.to_string()),
+        };
+    }
+
+    /// Only failures are handled here, a successful apply closes the view.
+    pub fn handle_apply_failed(&mut self, err: AppError) {
+        self.state = EditState::Failed(err.to_string());
+        self.scroll = 0;
+    }
+
+    fn lines(&self) -> Vec<Line<'_>> {
+        match &self.state {
+            EditState::Diffing => vec![Line::from("Comparing with the cluster...")],
+            EditState::Applying => vec![Line::from("Applying...")],
+            EditState::Diff(diff) if diff.is_empty() => {
+                vec![Line::from("No changes compared to the cluster.")]
+            }
+            EditState::Diff(diff) => diff.iter().map(|line| diff_line(line)).collect(),
+            EditState::Failed(err) => err
+                .lines()
+                .map(|line| Line::styled(line, Style::default().fg(Color::Red)))
+                .collect(),
+        }
+    }
+
+    pub fn draw(&mut self, area: Rect, frame: &mut Frame, is_focused: bool) {
+        let layouts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Min(3), Constraint::Length(3)])
+            .split(area);
+
+        // Borders take two lines.
+        self.page_height = layouts[0].height.saturating_sub(2) as usize;
+
+        let max_scroll = self.lines().len().saturating_sub(self.page_height);
+        self.scroll = self.scroll.min(max_scroll);
+
+        let target = self.session.resource.target();
+
+        let (title, keys) = match &self.state {
+            EditState::Diffing => (
+                loading_title(format!("Diff {target}").as_str(), true),
+                "Esc discard",
+            ),
+            EditState::Applying => (
+                loading_title(format!("Apply {target}").as_str(), true),
+                "Esc discard",
+            ),
+            EditState::Diff(diff) if diff.is_empty() => {
+                (format!("Diff {target}"), "e edit again | Esc discard")
+            }
+            EditState::Diff(_) => (
+                format!("Apply these changes to {target}?"),
+                "j/k scroll | a apply | e edit again | Esc discard",
+            ),
+            EditState::Failed(_) => (
+                format!("Failed to apply {target}"),
+                "j/k scroll | e edit again | Esc discard",
+            ),
+        };
+
+        let lines: Vec<Line> = self
+            .lines()
+            .into_iter()
+            .skip(self.scroll)
+            .take(self.page_height)
+            .collect();
+
+        frame.render_widget(
+            Paragraph::new(lines).block(build_block(title.as_str(), is_focused)),
+            layouts[0],
+        );
+
+        frame.render_widget(
+            Paragraph::new(keys).block(build_block("Keys", false)),
+            layouts[1],
+        );
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) {
+        let is_busy = matches!(self.state, EditState::Diffing | EditState::Applying);
+        let can_apply = matches!(&self.state, EditState::Diff(diff) if !diff.is_empty());
+
+        match key.code {
+            KeyCode::Esc => self.event_sender.send(AppEvent::CloseEdit),
+            KeyCode::Char('a') if can_apply => self.event_sender.send(AppEvent::ApplyEdit),
+            KeyCode::Char('e') if !is_busy => self.event_sender.send(AppEvent::ReopenEditor),
+            KeyCode::Char('j') | KeyCode::Down => self.scroll += 1,
+            KeyCode::Char('k') | KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.scroll += self.page_height / 2
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.scroll = self.scroll.saturating_sub(self.page_height / 2)
+            }
+            KeyCode::PageDown => self.scroll += self.page_height,
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(self.page_height),
+            _ => {}
+        }
+    }
+}
+
+fn diff_line(line: &str) -> Line<'_> {
+    let style = if line.starts_with("+++") || line.starts_with("---") || line.starts_with("diff ") {
+        Style::default().fg(Color::DarkGray)
+    } else if line.starts_with('+') {
+        Style::default().fg(Color::Green)
+    } else if line.starts_with('-') {
+        Style::default().fg(Color::Red)
+    } else if line.starts_with("@@") {
+        Style::default().fg(Color::Cyan)
+    } else {
+        Style::default()
+    };
+
+    Line::styled(line, style)
+}
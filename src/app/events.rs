@@ -1,29 +1,168 @@
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
 use anyhow::Context;
 use futures::{FutureExt, StreamExt};
 use ratatui::crossterm::{self, event::Event as CrosstermEvent};
-use tokio::sync::mpsc;
+use tokio::{
+    process::Command,
+    sync::{Notify, mpsc, watch},
+    time::{MissedTickBehavior, interval},
+};
 
 use crate::{
-    app::ActiveWindow,
+    app::{
+        ActiveWindow,
+        edit_view::EditSession,
+        jobs::Job,
+        logs_view::LogsSource,
+        side_bar::port_forwards::{Health, PortForward, Probe},
+    },
     error::{AppError, AppResult},
+    kubectl::{
+        StreamEvent,
+        deployments::Deployment,
+        manifest::{ManifestFormat, ResourceRef},
+        pods::{PodDetails, PodsWatchEvent},
+        services::Service,
+    },
 };
 
+const TICK_RATE: Duration = Duration::from_millis(250);
+
 pub enum AppEvent {
     Crossterm(CrosstermEvent),
+    /// Sent periodically to redraw time based state.
+    Tick,
     Focus(ActiveWindow),
     Quit,
+    JobFinished {
+        job: Job,
+        id: u64,
+        event: Box<AppEvent>,
+    },
+    CurrentContextLoaded(AppResult<String>),
+    ContextsLoaded(AppResult<Vec<String>>),
+    NamespacesLoaded(AppResult<Vec<String>>),
+    CancelJob(Job),
+    PortForwardStarted {
+        port_forward: Box<PortForward>,
+        result: AppResult<PortForward>,
+    },
+    PortForwardExited {
+        pid: u32,
+    },
+    PortForwardProbed {
+        probe: Probe,
+        health: Health,
+    },
+    /// Results of relaunching the forwards that were active in the last session.
+    PortForwardsRestored {
+        context: String,
+        results: Vec<(PortForward, AppResult<PortForward>)>,
+    },
+    ToggleRestorePortForwards,
     SelectNamespace(String),
-    PortForward {
+    SelectContext(String),
+    PortForward(PortForward),
+    /// Starts the forward after stopping our own forwards that hold its local ports.
+    ReplacePortForward(PortForward),
+    /// Adds `kubectl port-forward` processes started outside the app to the list.
+    AdoptPortForwards,
+    /// Forwards of `context` found running outside the app, ready to be adopted.
+    PortForwardsFound {
+        context: String,
+        result: AppResult<Vec<PortForward>>,
+    },
+    ClosePodsList,
+    OpenServices {
+        context: String,
+        namespace: String,
+    },
+    ServicesLoaded(AppResult<Vec<Service>>),
+    CloseServices,
+    OpenDeployments {
+        context: String,
+        namespace: String,
+    },
+    DeploymentsLoaded(AppResult<Vec<Deployment>>),
+    ReloadDeployments,
+    ScaleDeployment {
+        name: String,
+        replicas: u32,
+    },
+    RestartDeployment(String),
+    /// Result of a scale or a restart, whose rollout is followed from then on.
+    DeploymentUpdated {
+        name: String,
+        result: AppResult<String>,
+    },
+    RolloutStatus {
+        rollout_id: u64,
+        event: StreamEvent,
+    },
+    /// Shows the pods list limited to the pods matching `selector`.
+    ShowDeploymentPods {
+        name: String,
+        selector: BTreeMap<String, String>,
+    },
+    /// Returns from the pods of a deployment to the deployments list.
+    CloseDeploymentPods,
+    CloseDeployments,
+    OpenPodDetails {
+        context: String,
+        namespace: String,
         pod_name: String,
-        local_port: u16,
-        app_port: u16,
+    },
+    ReloadPodDetails,
+    PodDetailsLoaded(Box<AppResult<PodDetails>>),
+    ClosePodDetails,
+    OpenManifest(ResourceRef),
+    LoadManifest(ManifestFormat),
+    ManifestLoaded {
+        format: ManifestFormat,
+        result: AppResult<String>,
+    },
+    CloseManifest,
+    /// Fetches the resource and opens it in the user's editor.
+    EditResource(ResourceRef),
+    EditorReady(AppResult<EditSession>),
+    EditorClosed(EditSession),
+    ReopenEditor,
+    EditDiffLoaded(AppResult<String>),
+    ApplyEdit,
+    EditApplied(AppResult<String>),
+    /// Discards the edit.
+    CloseEdit,
+    DeletePods {
+        context: String,
         namespace: String,
+        names: Vec<String>,
+        grace_period: Option<u32>,
+        force: bool,
     },
-    ClosePodsList,
+    PodsDeleted {
+        context: String,
+        namespace: String,
+        names: Vec<String>,
+        result: AppResult<String>,
+    },
+    PodsWatch {
+        context: String,
+        namespace: String,
+        event: PodsWatchEvent,
+    },
+    OpenLogs(LogsSource),
+    LogsStream {
+        stream_id: u64,
+        event: StreamEvent,
+    },
+    CloseLogs,
+    RunInteractive(Command),
     ShowNotification(Log),
+    ToggleNotificationsHistory,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Log {
     Info(String),
     Warning(String),
@@ -33,6 +172,8 @@ pub enum Log {
 pub struct EventHandler {
     sender: EventSender,
     receiver: mpsc::UnboundedReceiver<AppEvent>,
+    paused: watch::Sender<bool>,
+    paused_ack: Arc<Notify>,
 }
 
 #[derive(Clone, Debug)]
@@ -49,16 +190,31 @@ impl EventSender {
 impl EventHandler {
     pub fn new() -> Self {
         let (sender, receiver) = mpsc::unbounded_channel();
+        let (paused, paused_receiver) = watch::channel(false);
+        let paused_ack = Arc::new(Notify::new());
 
-        let actor = EventTask::new(sender.clone());
+        let actor = EventTask::new(sender.clone(), paused_receiver, paused_ack.clone());
         tokio::spawn(async { actor.run().await });
 
         Self {
             sender: EventSender { sender },
             receiver,
+            paused,
+            paused_ack,
         }
     }
 
+    /// Stops reading the terminal so an interactive child process owns stdin.
+    /// Resolves once the crossterm reader has been dropped.
+    pub async fn pause(&self) {
+        self.paused.send_replace(true);
+        self.paused_ack.notified().await;
+    }
+
+    pub fn resume(&self) {
+        self.paused.send_replace(false);
+    }
+
     pub fn sender(&self) -> EventSender {
         self.sender.clone()
     }
@@ -74,26 +230,65 @@ impl EventHandler {
 
 struct EventTask {
     sender: mpsc::UnboundedSender<AppEvent>,
+    paused: watch::Receiver<bool>,
+    paused_ack: Arc<Notify>,
 }
 
 impl EventTask {
-    fn new(sender: mpsc::UnboundedSender<AppEvent>) -> Self {
-        Self { sender }
+    fn new(
+        sender: mpsc::UnboundedSender<AppEvent>,
+        paused: watch::Receiver<bool>,
+        paused_ack: Arc<Notify>,
+    ) -> Self {
+        Self {
+            sender,
+            paused,
+            paused_ack,
+        }
     }
 
-    async fn run(self) {
-        let mut reader = crossterm::event::EventStream::new();
+    async fn run(mut self) {
+        let mut tick = interval(TICK_RATE);
+        tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
 
         loop {
-            let crossterm_event = reader.next().fuse();
+            let mut reader = crossterm::event::EventStream::new();
+
+            loop {
+                let crossterm_event = reader.next().fuse();
+
+                tokio::select! {
+                    _ = self.sender.closed() => {
+                        return;
+                    }
 
-            tokio::select! {
-                _ = self.sender.closed() => {
-                    break;
+                    changed = self.paused.changed() => {
+                        if changed.is_err() {
+                            return;
+                        }
+
+                        if *self.paused.borrow_and_update() {
+                            break;
+                        }
+                    }
+
+                    _ = tick.tick() => {
+                        self.send(AppEvent::Tick);
+                    }
+
+                    Some(Ok(evt)) = crossterm_event => {
+                        self.send(AppEvent::Crossterm(evt));
+                    }
                 }
+            }
+
+            // Dropping the stream stops crossterm's background poll of stdin.
+            drop(reader);
+            self.paused_ack.notify_one();
 
-                Some(Ok(evt)) = crossterm_event => {
-                    self.send(AppEvent::Crossterm(evt));
+            while *self.paused.borrow_and_update() {
+                if self.paused.changed().await.is_err() {
+                    return;
                 }
             }
         }
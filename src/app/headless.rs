@@ -0,0 +1,213 @@
+use anyhow::Context;
+use serde::Serialize;
+
+use crate::{
+    app::{
+        cache::{self, AppCache, CacheLock},
+        side_bar::port_forwards::{PortForward, export_port_forwards, launch_port_forward},
+    },
+    cli::{PortForwardsAction, PortForwardsCommand},
+    error::{AppError, AppResult},
+    kubectl::{self, PortMapping},
+};
+
+pub use crate::app::side_bar::port_forwards::ExportFormat;
+
+/// A forward as printed by `pro-kube pf`.
+#[derive(Serialize)]
+struct ForwardStatus {
+    context: String,
+    namespace: String,
+    target: String,
+    ports: Vec<PortMapping>,
+    pid: Option<u32>,
+    running: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl ForwardStatus {
+    fn new(port_forward: &PortForward, error: Option<String>) -> Self {
+        let running = port_forward.is_running();
+
+        Self {
+            context: port_forward.context.clone(),
+            namespace: port_forward.namespace.clone(),
+            target: port_forward.target(),
+            ports: port_forward.port_mappings(),
+            pid: port_forward.pid.filter(|_| running),
+            running,
+            error,
+        }
+    }
+}
+
+/// Runs `pro-kube pf ...` against the forwards saved by the UI. Returns `false` when any of the
+/// selected forwards failed, so scripts can rely on the exit code.
+pub async fn run_port_forwards(command: PortForwardsCommand) -> AppResult<bool> {
+    // Held until the changed state is written back.
+    let _lock = match command.action {
+        PortForwardsAction::List | PortForwardsAction::Export => None,
+        _ => Some(CacheLock::exclusive().await?),
+    };
+
+    let Some(mut cache) = cache::read_cache().await? else {
+        return Err(AppError::CacheError(anyhow::anyhow!(
+            "no saved state found, add port forwards in the UI first"
+        )));
+    };
+
+    let context = command.context.clone().unwrap_or(cache.context.clone());
+    let port_forwards = saved_port_forwards(&mut cache, context.as_str())?;
+
+    let indexes: Vec<usize> = port_forwards
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| {
+            let is_default_selection = command.local_ports.is_empty() && !command.all;
+
+            command.all
+                || command.local_ports.contains(&item.local_port)
+                || (is_default_selection && command.action == PortForwardsAction::List)
+                || (is_default_selection
+                    && command.action == PortForwardsAction::Export
+                    && item.is_running())
+        })
+        .map(|(index, _)| index)
+        .collect();
+
+    if let Some(missing) = command
+        .local_ports
+        .iter()
+        .find(|port| !port_forwards.iter().any(|item| item.local_port == **port))
+    {
+        return Err(AppError::PortForwardError(anyhow::anyhow!(
+            "no saved port forward on local port {missing} in context {context}"
+        )));
+    }
+
+    if command.action == PortForwardsAction::Export {
+        let selected = indexes.iter().map(|&index| &port_forwards[index]);
+        print!("{}", export_port_forwards(selected, command.format)?);
+
+        return Ok(true);
+    }
+
+    let mut statuses = vec![];
+
+    for index in indexes {
+        let item = &mut port_forwards[index];
+
+        let result = match command.action {
+            PortForwardsAction::List | PortForwardsAction::Export => Ok(()),
+            PortForwardsAction::Start => start(item).await,
+            PortForwardsAction::Stop => stop(item),
+            PortForwardsAction::Restart => restart(item).await,
+        };
+
+        statuses.push(ForwardStatus::new(
+            item,
+            result.err().map(|err| err.to_string()),
+        ));
+    }
+
+    if command.action != PortForwardsAction::List {
+        cache::write_cache(&cache).await?;
+    }
+
+    print_statuses(&statuses, command.json)?;
+
+    Ok(statuses.iter().all(|status| status.error.is_none()))
+}
+
+fn saved_port_forwards<'a>(
+    cache: &'a mut AppCache,
+    context: &str,
+) -> AppResult<&'a mut Vec<PortForward>> {
+    let context_cache = cache.contexts.get_mut(context).ok_or_else(|| {
+        AppError::CacheError(anyhow::anyhow!("no saved state for context {context}"))
+    })?;
+
+    Ok(&mut context_cache.side_bar.port_forwards.list)
+}
+
+async fn start(port_forward: &mut PortForward) -> AppResult<()> {
+    if port_forward.is_running() {
+        return Ok(());
+    }
+
+    if port_forward.proxied {
+        return Err(AppError::PortForwardError(anyhow::anyhow!(
+            "{} is served by the built-in proxy, start it from the UI",
+            port_forward.target()
+        )));
+    }
+
+    *port_forward = launch_port_forward(port_forward.clone()).await?;
+    port_forward.was_active = true;
+    Ok(())
+}
+
+fn stop(port_forward: &mut PortForward) -> AppResult<()> {
+    if let Some(pid) = port_forward.pid
+        && port_forward.is_running()
+    {
+        kubectl::stop_port_forward(pid)?;
+    }
+
+    port_forward.pid = None;
+    port_forward.was_active = false;
+    Ok(())
+}
+
+async fn restart(port_forward: &mut PortForward) -> AppResult<()> {
+    stop(port_forward)?;
+
+    // kubectl needs a moment to exit and release the local ports.
+    kubectl::wait_for_ports_release(&port_forward.port_mappings()).await;
+
+    start(port_forward).await
+}
+
+fn print_statuses(statuses: &[ForwardStatus], json: bool) -> AppResult<()> {
+    if json {
+        let output =
+            serde_json::to_string_pretty(statuses).context("failed to serialize port forwards")?;
+
+        println!("{output}");
+        return Ok(());
+    }
+
+    println!(
+        "{:<8} {:<24} {:<40} {:<20} PID",
+        "STATUS", "PORTS", "TARGET", "NAMESPACE"
+    );
+
+    for status in statuses {
+        let ports = status
+            .ports
+            .iter()
+            .map(|ports| format!("{}->{}", ports.local_port, ports.app_port))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let state = match (&status.error, status.running) {
+            (Some(_), _) => "failed",
+            (None, true) => "running",
+            (None, false) => "stopped",
+        };
+
+        let pid = status.pid.map(|pid| pid.to_string()).unwrap_or_default();
+
+        println!(
+            "{state:<8} {ports:<24} {:<40} {:<20} {pid}",
+            status.target, status.namespace
+        );
+
+        if let Some(error) = &status.error {
+            eprintln!("  {}", error.trim_end().replace('\n', "\n  "));
+        }
+    }
+
+    Ok(())
+}
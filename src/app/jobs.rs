@@ -0,0 +1,107 @@
+use std::{collections::HashMap, future::Future};
+
+use tokio::task::AbortHandle;
+
+use crate::app::events::{AppEvent, EventSender};
+
+/// Identifies what a background job works on. Spawning a job with the same key cancels the
+/// previous one, so a stale result never overwrites a newer one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Job {
+    CurrentContext,
+    Contexts,
+    Namespaces,
+    Services,
+    Deployments,
+    UpdateDeployment(String),
+    PodDetails,
+    Manifest,
+    Edit,
+    DeletePods(Vec<String>),
+    PortForward { local_port: u16 },
+    ReplacePortForward { local_port: u16 },
+    ProbePortForward { local_port: u16 },
+    RestorePortForwards { context: String },
+    AdoptPortForwards,
+}
+
+#[derive(Debug)]
+struct RunningJob {
+    id: u64,
+    handle: AbortHandle,
+}
+
+/// Runs kubectl commands as tokio tasks so the UI keeps drawing and handling input.
+/// Every job reports back with a single `AppEvent` wrapped in `AppEvent::JobFinished`.
+#[derive(Debug)]
+pub struct JobRunner {
+    event_sender: EventSender,
+    running: HashMap<Job, RunningJob>,
+    next_id: u64,
+}
+
+impl JobRunner {
+    pub fn new(event_sender: EventSender) -> Self {
+        Self {
+            event_sender,
+            running: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    pub fn spawn<F>(&mut self, job: Job, task: F)
+    where
+        F: Future<Output = AppEvent> + Send + 'static,
+    {
+        self.cancel(&job);
+
+        self.next_id += 1;
+        let id = self.next_id;
+        let event_sender = self.event_sender.clone();
+        let finished_job = job.clone();
+
+        let handle = tokio::spawn(async move {
+            let event = task.await;
+
+            event_sender.send(AppEvent::JobFinished {
+                job: finished_job,
+                id,
+                event: Box::new(event),
+            });
+        });
+
+        self.running.insert(
+            job,
+            RunningJob {
+                id,
+                handle: handle.abort_handle(),
+            },
+        );
+    }
+
+    pub fn cancel(&mut self, job: &Job) {
+        if let Some(running) = self.running.remove(job) {
+            running.handle.abort();
+        }
+    }
+
+    /// Marks the job as done. Returns `false` when the result belongs to a job that has been
+    /// cancelled or replaced in the meantime and must be ignored.
+    pub fn finish(&mut self, job: &Job, id: u64) -> bool {
+        match self.running.get(job) {
+            Some(running) if running.id == id => {
+                self.running.remove(job);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_running(&self, job: &Job) -> bool {
+        self.running.contains_key(job)
+    }
+
+    pub fn any_running(&self, predicate: impl Fn(&Job) -> bool) -> bool {
+        self.running.keys().any(predicate)
+    }
+}
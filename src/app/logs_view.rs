@@ -0,0 +1,341 @@
+use std::{collections::VecDeque, path::PathBuf};
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::{Constraint, Direction, Layout, Rect},
+    text::Line,
+    widgets::Paragraph,
+};
+
+use crate::{
+    app::{
+        common::{build_block, get_highlight_style, highlight_matches},
+        events::{AppEvent, EventSender},
+    },
+    error::AppResult,
+    kubectl::{KubectlStream, StreamEvent, logs::stream_logs, stream_port_forward_log},
+};
+
+const MAX_LINES: usize = 10_000;
+
+/// Where the viewed lines come from.
+#[derive(Debug, Clone)]
+pub enum LogsSource {
+    Container {
+        context: String,
+        namespace: String,
+        pod_name: String,
+        container: String,
+    },
+    /// Output of a `kubectl port-forward` process, read from its log file.
+    PortForward { name: String, log_path: PathBuf },
+}
+
+pub struct LogsView {
+    source: LogsSource,
+    lines: VecDeque<String>,
+    scroll: usize,
+    page_height: usize,
+    follow: bool,
+    previous: bool,
+    search: String,
+    is_search_mod: bool,
+    current_match: Option<usize>,
+    stream: Option<KubectlStream>,
+    stream_id: u64,
+    stream_status: Option<String>,
+    event_sender: EventSender,
+}
+
+impl LogsView {
+    pub fn new(source: LogsSource, event_sender: EventSender) -> Self {
+        Self {
+            source,
+            lines: VecDeque::new(),
+            scroll: 0,
+            page_height: 0,
+            follow: true,
+            previous: false,
+            search: String::new(),
+            is_search_mod: false,
+            current_match: None,
+            stream: None,
+            stream_id: 0,
+            stream_status: None,
+            event_sender,
+        }
+    }
+
+    pub fn start(mut self) -> AppResult<Self> {
+        self.restart_stream()?;
+        Ok(self)
+    }
+
+    fn restart_stream(&mut self) -> AppResult<()> {
+        // Drop the old stream first so its process is killed before the new one starts.
+        self.stream = None;
+        self.stream_id += 1;
+        self.lines.clear();
+        self.scroll = 0;
+        self.current_match = None;
+        self.stream_status = None;
+
+        let stream_id = self.stream_id;
+        let event_sender = self.event_sender.clone();
+
+        let on_event = move |event| event_sender.send(AppEvent::LogsStream { stream_id, event });
+
+        self.stream = Some(match &self.source {
+            LogsSource::Container {
+                context,
+                namespace,
+                pod_name,
+                container,
+            } => stream_logs(
+                context.as_str(),
+                namespace.as_str(),
+                pod_name.as_str(),
+                container.as_str(),
+                self.previous,
+                on_event,
+            )?,
+            LogsSource::PortForward { log_path, .. } => {
+                stream_port_forward_log(log_path.as_path(), on_event)?
+            }
+        });
+
+        Ok(())
+    }
+
+    pub fn source(&self) -> &LogsSource {
+        &self.source
+    }
+
+    pub fn handle_stream_event(&mut self, stream_id: u64, event: StreamEvent) {
+        if stream_id != self.stream_id {
+            return;
+        }
+
+        match event {
+            StreamEvent::Stdout(line) | StreamEvent::Stderr(line) => self.push_line(line),
+            StreamEvent::Exited(code) => {
+                self.stream_status = Some(match code {
+                    Some(0) => "stream ended".to_string(),
+                    Some(code) => format!("stream ended with exit code {code}"),
+                    None => "stream was terminated".to_string(),
+                });
+            }
+        }
+    }
+
+    fn push_line(&mut self, line: String) {
+        self.lines.push_back(line);
+
+        if self.lines.len() > MAX_LINES {
+            self.lines.pop_front();
+            self.scroll = self.scroll.saturating_sub(1);
+            self.current_match = self.current_match.and_then(|i| i.checked_sub(1));
+        }
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.lines.len().saturating_sub(self.page_height)
+    }
+
+    pub fn draw(&mut self, area: Rect, frame: &mut Frame, is_focused: bool) {
+        let layouts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Min(3), Constraint::Length(3)])
+            .split(area);
+
+        // Borders take two lines.
+        self.page_height = layouts[0].height.saturating_sub(2) as usize;
+
+        if self.follow {
+            self.scroll = self.max_scroll();
+        }
+
+        let lines: Vec<Line> = self
+            .lines
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(self.page_height)
+            .map(|(index, line)| {
+                if Some(index) == self.current_match {
+                    return Line::from(line.as_str()).style(get_highlight_style());
+                }
+
+                highlight_matches(line, self.search.as_str())
+            })
+            .collect();
+
+        let follow_status = if self.follow { "following" } else { "paused" };
+
+        let mut title = match &self.source {
+            LogsSource::Container {
+                pod_name,
+                container,
+                ..
+            } => format!("Logs {pod_name}/{container} [{follow_status}]"),
+            LogsSource::PortForward { name, .. } => {
+                format!("Port forward logs {name} [{follow_status}]")
+            }
+        };
+
+        if self.previous {
+            title.push_str(" [previous]");
+        }
+
+        if let Some(status) = &self.stream_status {
+            title.push_str(format!(" ({status})").as_str());
+        }
+
+        let logs_widget = Paragraph::new(lines).block(build_block(
+            title.as_str(),
+            is_focused && !self.is_search_mod,
+        ));
+
+        frame.render_widget(logs_widget, layouts[0]);
+
+        let footer = if self.is_search_mod || !self.search.is_empty() {
+            Paragraph::new(self.search.as_str()).block(build_block("Search", self.is_search_mod))
+        } else {
+            let keys = match self.source {
+                LogsSource::Container { .. } => {
+                    "j/k scroll | f follow | / search | n/N next/prev | p previous | Esc close"
+                }
+                LogsSource::PortForward { .. } => {
+                    "j/k scroll | f follow | / search | n/N next/prev | Esc close"
+                }
+            };
+
+            Paragraph::new(keys).block(build_block("Keys", false))
+        };
+
+        frame.render_widget(footer, layouts[1]);
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) -> AppResult<()> {
+        if self.is_search_mod {
+            match key.code {
+                KeyCode::Enter => {
+                    self.is_search_mod = false;
+                    self.current_match = None;
+                    self.find_next_match();
+                }
+                KeyCode::Esc => {
+                    self.search.clear();
+                    self.is_search_mod = false;
+                    self.current_match = None;
+                }
+                KeyCode::Backspace => {
+                    self.search.pop();
+                }
+                KeyCode::Char(ch) => self.search.push(ch),
+                _ => {}
+            };
+
+            return Ok(());
+        }
+
+        match key.code {
+            KeyCode::Char('q') => self.event_sender.send(AppEvent::Quit),
+            KeyCode::Esc => self.event_sender.send(AppEvent::CloseLogs),
+            KeyCode::Char('j') | KeyCode::Down => self.scroll_down(1),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll_up(1),
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.scroll_down(self.page_height / 2)
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.scroll_up(self.page_height / 2)
+            }
+            KeyCode::PageDown => self.scroll_down(self.page_height),
+            KeyCode::PageUp => self.scroll_up(self.page_height),
+            KeyCode::Char('g') | KeyCode::Home => {
+                self.follow = false;
+                self.scroll = 0;
+            }
+            KeyCode::Char('G') | KeyCode::End => self.follow = true,
+            KeyCode::Char('f') | KeyCode::Char(' ') => {
+                if !self.follow {
+                    self.follow = true;
+                } else {
+                    self.follow = false;
+                    self.scroll = self.max_scroll();
+                }
+            }
+            KeyCode::Char('/') => self.is_search_mod = true,
+            KeyCode::Char('n') => self.find_next_match(),
+            KeyCode::Char('N') => self.find_prev_match(),
+            KeyCode::Char('p') if matches!(self.source, LogsSource::Container { .. }) => {
+                self.previous = !self.previous;
+                self.follow = true;
+                self.restart_stream()?;
+            }
+            _ => {}
+        };
+
+        Ok(())
+    }
+
+    fn scroll_down(&mut self, lines: usize) {
+        self.scroll = (self.scroll + lines).min(self.max_scroll());
+
+        if self.scroll == self.max_scroll() {
+            self.follow = true;
+        }
+    }
+
+    fn scroll_up(&mut self, lines: usize) {
+        self.follow = false;
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    fn find_next_match(&mut self) {
+        if self.search.is_empty() {
+            return;
+        }
+
+        let start = self.current_match.map(|i| i + 1).unwrap_or(self.scroll);
+
+        let found = (start..self.lines.len())
+            .chain(0..start)
+            .find(|&index| self.lines[index].contains(&self.search));
+
+        self.jump_to_match(found);
+    }
+
+    fn find_prev_match(&mut self) {
+        if self.search.is_empty() {
+            return;
+        }
+
+        let start = self.current_match.unwrap_or(self.scroll + self.page_height);
+        let start = start.min(self.lines.len());
+
+        let found = (0..start)
+            .rev()
+            .chain((start..self.lines.len()).rev())
+            .find(|&index| self.lines[index].contains(&self.search));
+
+        self.jump_to_match(found);
+    }
+
+    fn jump_to_match(&mut self, found: Option<usize>) {
+        let Some(index) = found else {
+            return;
+        };
+
+        self.current_match = Some(index);
+        self.follow = false;
+
+        let page_end = self.scroll + self.page_height;
+        if index < self.scroll || index >= page_end {
+            self.scroll = index
+                .saturating_sub(self.page_height / 2)
+                .min(self.max_scroll());
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use std::ops::Range;
+
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+use crate::kubectl::manifest::ManifestFormat;
+
+const KEY_STYLE: Style = Style::new().fg(Color::Cyan);
+const STRING_STYLE: Style = Style::new().fg(Color::Green);
+/// Numbers, booleans and nulls.
+const SCALAR_STYLE: Style = Style::new().fg(Color::Magenta);
+const COMMENT_STYLE: Style = Style::new().fg(Color::DarkGray);
+
+/// One styled line per line of the manifest.
+pub(super) fn highlight(lines: &[String], format: ManifestFormat) -> Vec<Line<'static>> {
+    match format {
+        ManifestFormat::Yaml => highlight_yaml(lines),
+        ManifestFormat::Json => lines.iter().map(|line| json_line(line)).collect(),
+    }
+}
+
+/// Lines of `metadata.managedFields`, the key's own line included. Server side apply fills it
+/// with bookkeeping that is usually longer than the rest of the manifest.
+pub(super) fn managed_fields_range(
+    lines: &[String],
+    format: ManifestFormat,
+) -> Option<Range<usize>> {
+    let (key, closing) = match format {
+        ManifestFormat::Yaml => ("managedFields:", None),
+        ManifestFormat::Json => ("\"managedFields\": [", Some(']')),
+    };
+
+    let start = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with(key))?;
+
+    let indent = indent_of(&lines[start]);
+
+    let end = lines
+        .iter()
+        .enumerate()
+        .skip(start + 1)
+        .find(|(_, line)| {
+            let trimmed = line.trim_start();
+
+            if trimmed.is_empty() {
+                return false;
+            }
+
+            match closing {
+                // The list items of a YAML key sit at the key's own indent.
+                None => {
+                    indent_of(line) < indent
+                        || (indent_of(line) == indent && !trimmed.starts_with("- "))
+                }
+                Some(closing) => indent_of(line) == indent && trimmed.starts_with(closing),
+            }
+        })
+        .map(|(index, _)| match closing {
+            None => index,
+            Some(_) => index + 1,
+        })
+        .unwrap_or(lines.len());
+
+    Some(start..end)
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn highlight_yaml(lines: &[String]) -> Vec<Line<'static>> {
+    // Column of the key owning the block scalar (`|` or `>`) being read, if any.
+    let mut block_scalar: Option<usize> = None;
+
+    lines
+        .iter()
+        .map(|line| {
+            if let Some(key_column) = block_scalar {
+                if line.trim().is_empty() || indent_of(line) > key_column {
+                    return Line::styled(line.clone(), STRING_STYLE);
+                }
+
+                block_scalar = None;
+            }
+
+            let (line, key_column) = yaml_line(line);
+            block_scalar = key_column;
+            line
+        })
+        .collect()
+}
+
+/// Returns the styled line and, when it opens a block scalar, the column of its key.
+fn yaml_line(line: &str) -> (Line<'static>, Option<usize>) {
+    let mut column = indent_of(line);
+    let mut spans = vec![Span::raw(line[..column].to_string())];
+    let mut rest = &line[column..];
+
+    while let Some(item) = rest.strip_prefix("- ") {
+        spans.push(Span::raw("- "));
+        rest = item;
+        column += 2;
+    }
+
+    if rest.starts_with('#') {
+        spans.push(Span::styled(rest.to_string(), COMMENT_STYLE));
+        return (Line::from(spans), None);
+    }
+
+    let Some((key, value)) = split_yaml_key(rest) else {
+        spans.extend(value_spans(rest));
+        return (Line::from(spans), None);
+    };
+
+    spans.push(Span::styled(key.to_string(), KEY_STYLE));
+    spans.push(Span::raw(":"));
+    spans.extend(value_spans(value));
+
+    let opens_block = value.trim_start().starts_with(['|', '>']);
+
+    (Line::from(spans), opens_block.then_some(column))
+}
+
+/// Splits `key: value` into the key and everything after its colon.
+fn split_yaml_key(text: &str) -> Option<(&str, &str)> {
+    if text.starts_with(['"', '\'']) {
+        let end = string_end(text)?;
+
+        return text[end..]
+            .strip_prefix(':')
+            .map(|value| (&text[..end], value));
+    }
+
+    if let Some(position) = text.find(": ") {
+        return Some((&text[..position], &text[position + 1..]));
+    }
+
+    text.strip_suffix(':').map(|key| (key, ""))
+}
+
+fn json_line(line: &str) -> Line<'static> {
+    let indent = indent_of(line);
+    let mut spans = vec![Span::raw(line[..indent].to_string())];
+    let rest = &line[indent..];
+
+    if rest.starts_with('"')
+        && let Some(end) = string_end(rest)
+        && let Some(value) = rest[end..].strip_prefix(':')
+    {
+        spans.push(Span::styled(rest[..end].to_string(), KEY_STYLE));
+        spans.push(Span::raw(":"));
+        spans.extend(value_spans(value));
+    } else {
+        spans.extend(value_spans(rest));
+    }
+
+    Line::from(spans)
+}
+
+/// Byte index right after the closing quote of the string `text` starts with.
+fn string_end(text: &str) -> Option<usize> {
+    let quote = text.chars().next()?;
+    let mut is_escaped = false;
+
+    for (index, ch) in text.char_indices().skip(1) {
+        match ch {
+            _ if is_escaped => is_escaped = false,
+            '\\' if quote == '"' => is_escaped = true,
+            _ if ch == quote => return Some(index + 1),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Scalar values with their surrounding whitespace and JSON's trailing comma left unstyled.
+fn value_spans(text: &str) -> Vec<Span<'static>> {
+    let trimmed = text.trim_start();
+    let leading = &text[..text.len() - trimmed.len()];
+
+    let (value, trailing) = match trimmed.strip_suffix(',') {
+        Some(value) => (value, ","),
+        None => (trimmed, ""),
+    };
+
+    let style = match value {
+        "" | "{}" | "[]" | "{" | "[" | "}" | "]" => Style::default(),
+        "true" | "false" | "null" | "~" => SCALAR_STYLE,
+        _ if value.starts_with(['|', '>']) => Style::default(),
+        _ if value.parse::<f64>().is_ok() => SCALAR_STYLE,
+        _ => STRING_STYLE,
+    };
+
+    [
+        Span::raw(leading.to_string()),
+        Span::styled(value.to_string(), style),
+        Span::raw(trailing),
+    ]
+    .into_iter()
+    .filter(|span| !span.content.is_empty())
+    .collect()
+}
@@ -0,0 +1,336 @@
+mod highlight;
+
+use std::ops::Range;
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    app::{
+        MainWindow,
+        common::{build_block, get_highlight_style, highlight_matches, loading_title},
+        events::{AppEvent, EventSender},
+        manifest_view::highlight::{highlight, managed_fields_range},
+    },
+    kubectl::manifest::{ManifestFormat, ResourceRef},
+};
+
+const LINE_NUMBER_STYLE: Style = Style::new().fg(Color::DarkGray);
+
+/// Manifest of any resource as kubectl prints it, opened from the resource lists.
+pub struct ManifestView {
+    resource: ResourceRef,
+    format: ManifestFormat,
+    /// Window to return to on close.
+    opened_from: MainWindow,
+    lines: Vec<String>,
+    highlighted: Vec<Line<'static>>,
+    managed_fields: Option<Range<usize>>,
+    is_folded: bool,
+    /// Indices of the shown lines. A folded block is shown as a single row, its first line.
+    rows: Vec<usize>,
+    scroll: usize,
+    page_height: usize,
+    search: String,
+    is_search_mod: bool,
+    /// Index into `rows`.
+    current_match: Option<usize>,
+    is_loading: bool,
+    event_sender: EventSender,
+}
+
+impl ManifestView {
+    pub fn new(resource: ResourceRef, opened_from: MainWindow, event_sender: EventSender) -> Self {
+        Self {
+            resource,
+            format: ManifestFormat::Yaml,
+            opened_from,
+            lines: vec![],
+            highlighted: vec![],
+            managed_fields: None,
+            is_folded: true,
+            rows: vec![],
+            scroll: 0,
+            page_height: 0,
+            search: String::new(),
+            is_search_mod: false,
+            current_match: None,
+            is_loading: true,
+            event_sender,
+        }
+    }
+
+    pub fn resource(&self) -> &ResourceRef {
+        &self.resource
+    }
+
+    pub fn format(&self) -> ManifestFormat {
+        self.format
+    }
+
+    pub fn opened_from(&self) -> MainWindow {
+        self.opened_from
+    }
+
+    pub fn set_loading(&mut self, is_loading: bool) {
+        self.is_loading = is_loading;
+    }
+
+    pub fn update(&mut self, format: ManifestFormat, manifest: String) {
+        if format != self.format {
+            self.format = format;
+            self.scroll = 0;
+        }
+
+        self.lines = manifest.lines().map(String::from).collect();
+        self.highlighted = highlight(&self.lines, self.format);
+        self.managed_fields = managed_fields_range(&self.lines, self.format);
+        self.current_match = None;
+        self.update_rows();
+    }
+
+    fn update_rows(&mut self) {
+        self.rows = match (&self.managed_fields, self.is_folded) {
+            (Some(folded), true) => (0..self.lines.len())
+                .filter(|index| !folded.contains(index) || *index == folded.start)
+                .collect(),
+            _ => (0..self.lines.len()).collect(),
+        };
+
+        self.scroll = self.scroll.min(self.max_scroll());
+    }
+
+    fn folded_range(&self, line_index: usize) -> Option<&Range<usize>> {
+        self.managed_fields
+            .as_ref()
+            .filter(|folded| self.is_folded && folded.start == line_index)
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.rows.len().saturating_sub(self.page_height)
+    }
+
+    pub fn draw(&mut self, area: Rect, frame: &mut Frame, is_focused: bool) {
+        let layouts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Min(3), Constraint::Length(3)])
+            .split(area);
+
+        // Borders take two lines.
+        self.page_height = layouts[0].height.saturating_sub(2) as usize;
+        self.scroll = self.scroll.min(self.max_scroll());
+
+        let number_width = self.lines.len().to_string().len();
+
+        let lines: Vec<Line> = self
+            .rows
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(self.page_height)
+            .map(|(row, &index)| {
+                let number =
+                    Span::styled(format!("{:>number_width$} ", index + 1), LINE_NUMBER_STYLE);
+
+                let mut line = match self.folded_range(index) {
+                    Some(folded) => Line::from(vec![
+                        Span::raw(self.lines[index].clone()),
+                        Span::styled(
+                            format!(" ⋯ {} lines folded", folded.len() - 1),
+                            LINE_NUMBER_STYLE,
+                        ),
+                    ]),
+                    None if Some(row) == self.current_match => {
+                        Line::from(self.lines[index].as_str()).style(get_highlight_style())
+                    }
+                    None if !self.search.is_empty() && self.lines[index].contains(&self.search) => {
+                        highlight_matches(self.lines[index].as_str(), self.search.as_str())
+                    }
+                    None => self.highlighted[index].clone(),
+                };
+
+                line.spans.insert(0, number);
+                line
+            })
+            .collect();
+
+        let title = loading_title(
+            format!(
+                "Manifest {} [{}]",
+                self.resource.target(),
+                self.format.name()
+            )
+            .as_str(),
+            self.is_loading,
+        );
+
+        frame.render_widget(
+            Paragraph::new(lines).block(build_block(
+                title.as_str(),
+                is_focused && !self.is_search_mod,
+            )),
+            layouts[0],
+        );
+
+        let footer = if self.is_search_mod || !self.search.is_empty() {
+            Paragraph::new(self.search.as_str()).block(build_block("Search", self.is_search_mod))
+        } else {
+            let toggle = match self.format {
+                ManifestFormat::Yaml => "t JSON",
+                ManifestFormat::Json => "t YAML",
+            };
+
+            Paragraph::new(format!(
+                "j/k scroll | / search | n/N next/prev | z fold | {toggle} | e edit | r reload | Esc close"
+            ))
+            .block(build_block("Keys", false))
+        };
+
+        frame.render_widget(footer, layouts[1]);
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) {
+        if self.is_search_mod {
+            match key.code {
+                KeyCode::Enter => {
+                    self.is_search_mod = false;
+                    self.current_match = None;
+                    self.find_next_match();
+                }
+                KeyCode::Esc => {
+                    self.search.clear();
+                    self.is_search_mod = false;
+                    self.current_match = None;
+                }
+                KeyCode::Backspace => {
+                    self.search.pop();
+                }
+                KeyCode::Char(ch) => self.search.push(ch),
+                _ => {}
+            };
+
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char('q') => self.event_sender.send(AppEvent::Quit),
+            KeyCode::Esc => self.event_sender.send(AppEvent::CloseManifest),
+            KeyCode::Char('j') | KeyCode::Down => self.scroll_down(1),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll_up(1),
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.scroll_down(self.page_height / 2)
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.scroll_up(self.page_height / 2)
+            }
+            KeyCode::PageDown => self.scroll_down(self.page_height),
+            KeyCode::PageUp => self.scroll_up(self.page_height),
+            KeyCode::Char('g') | KeyCode::Home => self.scroll = 0,
+            KeyCode::Char('G') | KeyCode::End => self.scroll = self.max_scroll(),
+            KeyCode::Char('/') => self.is_search_mod = true,
+            KeyCode::Char('n') => self.find_next_match(),
+            KeyCode::Char('N') => self.find_prev_match(),
+            KeyCode::Char('z') => self.toggle_fold(),
+            KeyCode::Char('t') => self
+                .event_sender
+                .send(AppEvent::LoadManifest(self.format.toggle())),
+            KeyCode::Char('r') => self.event_sender.send(AppEvent::LoadManifest(self.format)),
+            KeyCode::Char('e') => self
+                .event_sender
+                .send(AppEvent::EditResource(self.resource.clone())),
+            _ => {}
+        };
+    }
+
+    fn toggle_fold(&mut self) {
+        let Some(folded) = self.managed_fields.clone() else {
+            return;
+        };
+
+        // Keep the line at the top of the page in place.
+        let top_line = self.rows.get(self.scroll).copied();
+        let current_line = self.current_match.map(|row| self.rows[row]);
+
+        self.is_folded = !self.is_folded;
+        self.update_rows();
+
+        let row_of = |line: usize| {
+            let line = if self.is_folded && folded.contains(&line) {
+                folded.start
+            } else {
+                line
+            };
+
+            self.rows.iter().position(|&index| index == line)
+        };
+
+        self.current_match = current_line.and_then(row_of);
+
+        if let Some(row) = top_line.and_then(row_of) {
+            self.scroll = row.min(self.max_scroll());
+        }
+    }
+
+    fn scroll_down(&mut self, lines: usize) {
+        self.scroll = (self.scroll + lines).min(self.max_scroll());
+    }
+
+    fn scroll_up(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    fn row_matches(&self, row: usize) -> bool {
+        self.lines[self.rows[row]].contains(&self.search)
+    }
+
+    fn find_next_match(&mut self) {
+        if self.search.is_empty() {
+            return;
+        }
+
+        let start = self.current_match.map(|i| i + 1).unwrap_or(self.scroll);
+
+        let found = (start..self.rows.len())
+            .chain(0..start)
+            .find(|&row| self.row_matches(row));
+
+        self.jump_to_match(found);
+    }
+
+    fn find_prev_match(&mut self) {
+        if self.search.is_empty() {
+            return;
+        }
+
+        let start = self.current_match.unwrap_or(self.scroll + self.page_height);
+        let start = start.min(self.rows.len());
+
+        let found = (0..start)
+            .rev()
+            .chain((start..self.rows.len()).rev())
+            .find(|&row| self.row_matches(row));
+
+        self.jump_to_match(found);
+    }
+
+    fn jump_to_match(&mut self, found: Option<usize>) {
+        let Some(row) = found else {
+            return;
+        };
+
+        self.current_match = Some(row);
+
+        let page_end = self.scroll + self.page_height;
+        if row < self.scroll || row >= page_end {
+            self.scroll = row
+                .saturating_sub(self.page_height / 2)
+                .min(self.max_scroll());
+        }
+    }
+}
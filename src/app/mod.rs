@@ -1,86 +1,562 @@
 pub mod cache;
 mod common;
+mod contexts_list;
+mod deployments_list;
+mod edit_view;
 mod events;
+pub mod headless;
+mod jobs;
+mod logs_view;
+mod manifest_view;
 mod namespaces_list;
+mod notifications;
+mod pod_details;
 mod pods_list;
+mod services_list;
 mod side_bar;
 
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+    time::Duration,
+};
+
 use anyhow::Context;
+use futures::future::join_all;
 use ratatui::{
     DefaultTerminal, Frame,
     crossterm::event::{Event, KeyEvent, KeyEventKind},
     layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    widgets::Paragraph,
 };
 use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    process::Command,
+    time::sleep,
+};
 
 use crate::{
     app::{
-        cache::AppCache,
-        events::{AppEvent, EventHandler},
+        cache::{AppCache, CacheLock, ContextCache},
+        contexts_list::ContextsList,
+        deployments_list::DeploymentsList,
+        edit_view::{EditSession, EditView},
+        events::{AppEvent, EventHandler, Log},
+        jobs::{Job, JobRunner},
+        logs_view::{LogsSource, LogsView},
+        manifest_view::ManifestView,
         namespaces_list::NamespacesList,
+        notifications::Notifications,
+        pod_details::PodDetailsView,
         pods_list::PodsList,
-        side_bar::SideBar,
+        services_list::ServicesList,
+        side_bar::{
+            SideBar,
+            port_forwards::{
+                PortForward, PortForwardsList, find_outside_port_forwards, launch_port_forward,
+            },
+        },
     },
     error::AppResult,
-    kubectl::namespace,
+    kubectl::{
+        self, context, deployments,
+        edit::{apply_manifest, diff_manifest},
+        manifest::{self, ManifestFormat},
+        namespace, pods, services,
+    },
 };
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
-enum ActiveWindow {
+pub enum ActiveWindow {
     Main(MainWindow),
     SideBar(SideBarWindow),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
-enum SideBarWindow {
+pub enum SideBarWindow {
     RecentNamespaces,
     RecentPortForwards,
+    PortForwardProfiles,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
-enum MainWindow {
+pub enum MainWindow {
     Namespaces,
     Pods,
+    Services,
+    Deployments,
+    PodDetails,
+    Logs,
+    Manifest,
+    Edit,
+    Contexts,
 }
 
 pub struct App {
+    context: String,
+    context_caches: HashMap<String, ContextCache>,
+    contexts: ContextsList,
     namespaces: NamespacesList,
     pods: Option<PodsList>,
+    services: Option<ServicesList>,
+    deployments: Option<DeploymentsList>,
+    pod_details: Option<PodDetailsView>,
+    logs: Option<LogsView>,
+    manifest: Option<ManifestView>,
+    edit: Option<EditView>,
     side_bar: SideBar,
     exit: bool,
     main_window: MainWindow,
     active_window: ActiveWindow,
     event_handler: EventHandler,
+    jobs: JobRunner,
+    notifications: Notifications,
+    interactive_command: Option<Command>,
+    /// Sent once the interactive command exits.
+    after_interactive: Option<AppEvent>,
+    restore_port_forwards: bool,
+    /// Keeps `pro-kube pf` from changing the saved state while it would be overwritten on quit.
+    cache_lock: Option<CacheLock>,
 }
 
 impl App {
     pub async fn run(&mut self, terminal: &mut DefaultTerminal) -> AppResult<()> {
-        let cache = cache::read_cache().await;
+        match CacheLock::shared().await {
+            Ok(lock) => self.cache_lock = Some(lock),
+            Err(err) => self.notifications.push(Log::Error(err.to_string())),
+        }
 
-        match cache {
-            Some(cache) => self.merge_cache(cache),
-            None => {
-                let namespaces = namespace::get_namespaces()
-                    .await
-                    .context("Failed to download namespaces")?;
+        let cache = cache::read_cache().await.unwrap_or_else(|err| {
+            self.notifications.push(Log::Error(err.to_string()));
+            None
+        });
 
-                self.namespaces.update_list(namespaces);
+        match cache {
+            Some(cache) => {
+                if !self.merge_cache(cache) {
+                    self.load_namespaces();
+                }
             }
+            None => self.jobs.spawn(Job::CurrentContext, async {
+                AppEvent::CurrentContextLoaded(context::get_current_context().await)
+            }),
         };
 
         while !self.exit {
             terminal.draw(|frame| self.draw(frame))?;
-            self.handle_events().await?;
+
+            let event = self.event_handler.next().await?;
+
+            // Failed commands are reported to the user instead of terminating the app.
+            if let Err(err) = self.handle_event(event).await {
+                self.notifications.push(Log::Error(err.to_string()));
+            }
+
+            if let Some(command) = self.interactive_command.take() {
+                if let Err(err) = self.run_interactive(terminal, command).await {
+                    self.notifications.push(Log::Error(err.to_string()));
+                }
+
+                if let Some(event) = self.after_interactive.take() {
+                    self.event_handler.sender().send(event);
+                }
+            }
         }
 
         Ok(())
     }
 
+    /// Leaves the alternate screen, hands the terminal over to `command` and restores the UI
+    /// once it exits.
+    async fn run_interactive(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        mut command: Command,
+    ) -> AppResult<()> {
+        self.event_handler.pause().await;
+        ratatui::restore();
+
+        let status = command.status().await;
+
+        // Keep the output on screen when the process fails, e.g. the image has no shell.
+        let failed = match &status {
+            Ok(status) if status.success() => None,
+            Ok(status) => Some(format!("process exited with {status}")),
+            Err(err) => Some(format!("failed to start process: {err}")),
+        };
+
+        if let Some(message) = failed {
+            print!("\n{message}. Press Enter to return...");
+            io::stdout().flush()?;
+
+            let mut line = String::new();
+            BufReader::new(tokio::io::stdin())
+                .read_line(&mut line)
+                .await?;
+        }
+
+        *terminal = ratatui::init();
+        terminal.clear()?;
+        self.event_handler.resume();
+
+        Ok(())
+    }
+
+    fn load_namespaces(&mut self) {
+        let context = self.context.clone();
+
+        self.jobs.spawn(Job::Namespaces, async move {
+            AppEvent::NamespacesLoaded(namespace::get_namespaces(context.as_str()).await)
+        });
+    }
+
+    fn load_deployments(&mut self) {
+        let Some((context, namespace)) = self.deployments.as_ref().map(|view| view.target()) else {
+            return;
+        };
+
+        self.jobs.spawn(Job::Deployments, async move {
+            AppEvent::DeploymentsLoaded(
+                deployments::get_deployments(context.as_str(), namespace.as_str()).await,
+            )
+        });
+    }
+
+    fn load_pod_details(&mut self) {
+        let Some((context, namespace, pod_name)) =
+            self.pod_details.as_ref().map(|view| view.target())
+        else {
+            return;
+        };
+
+        self.jobs.spawn(Job::PodDetails, async move {
+            let result =
+                pods::get_pod_details(context.as_str(), namespace.as_str(), pod_name.as_str())
+                    .await;
+
+            AppEvent::PodDetailsLoaded(Box::new(result))
+        });
+    }
+
+    fn load_manifest(&mut self, format: ManifestFormat) {
+        let Some(resource) = self.manifest.as_ref().map(|view| view.resource().clone()) else {
+            return;
+        };
+
+        self.jobs.spawn(Job::Manifest, async move {
+            AppEvent::ManifestLoaded {
+                format,
+                result: manifest::get_manifest(&resource, format).await,
+            }
+        });
+    }
+
+    /// Suspends the UI for the editor, the edit goes on with `EditorClosed`.
+    fn open_editor(&mut self, session: EditSession) {
+        self.interactive_command = Some(session.editor_command());
+        self.after_interactive = Some(AppEvent::EditorClosed(session));
+    }
+
+    /// Discards the edit in progress, if any, and returns to where it was started from.
+    fn close_edit(&mut self) {
+        self.jobs.cancel(&Job::Edit);
+
+        let Some(edit) = self.edit.take() else {
+            return;
+        };
+
+        edit.session().discard();
+
+        if self.main_window == MainWindow::Edit {
+            self.active_window = ActiveWindow::Main(edit.opened_from());
+            self.main_window = edit.opened_from();
+        }
+    }
+
+    fn spawn_port_forward(&mut self, port_forward: PortForward, delay: Duration) {
+        let job = Job::PortForward {
+            local_port: port_forward.local_port,
+        };
+
+        self.jobs.spawn(job, async move {
+            sleep(delay).await;
+            let result = launch_port_forward(port_forward.clone()).await;
+
+            AppEvent::PortForwardStarted {
+                port_forward: Box::new(port_forward),
+                result,
+            }
+        });
+    }
+
+    fn probe_port_forwards(&mut self) {
+        for probe in self.side_bar.port_forwards.due_probes() {
+            let local_port = probe.local_port;
+
+            self.jobs
+                .spawn(Job::ProbePortForward { local_port }, async move {
+                    let health = probe.run().await;
+                    AppEvent::PortForwardProbed { probe, health }
+                });
+        }
+    }
+
+    fn load_contexts(&mut self) {
+        self.jobs.spawn(Job::Contexts, async {
+            AppEvent::ContextsLoaded(context::get_contexts().await)
+        });
+    }
+
+    fn context_cache(&self) -> ContextCache {
+        ContextCache {
+            namespaces: self.namespaces.clone().into(),
+            pods: self.pods.clone().map(|p| p.into()),
+            side_bar: self.side_bar.clone().into(),
+        }
+    }
+
+    fn restore_context_cache(&mut self, cache: ContextCache) {
+        self.namespaces
+            .restore_from_cache(cache.namespaces.namespace_list.into());
+
+        self.pods = cache
+            .pods
+            .map(|pods_cache| PodsList::from_cache(pods_cache, self.event_handler.sender()));
+
+        self.side_bar = SideBar::from_cache(cache.side_bar, self.event_handler.sender());
+    }
+
+    /// Hands `apply` the forwards of `context`, taken from its saved state when it is not the
+    /// shown one, so results of jobs that outlived a context switch land on the right list.
+    /// Returns `None` for a context the app holds no state of.
+    fn with_port_forwards<T>(
+        &mut self,
+        context: &str,
+        apply: impl FnOnce(&mut PortForwardsList) -> T,
+    ) -> Option<T> {
+        if context == self.context {
+            return Some(apply(&mut self.side_bar.port_forwards));
+        }
+
+        let event_sender = self.event_handler.sender();
+        let cache = self.context_caches.get_mut(context)?;
+
+        let mut port_forwards =
+            PortForwardsList::from_live_cache(cache.side_bar.port_forwards.clone(), event_sender);
+        let result = apply(&mut port_forwards);
+        cache.side_bar.port_forwards = port_forwards.into();
+
+        Some(result)
+    }
+
+    /// The context whose forwards run `pid`, shown or not.
+    fn context_of_process(&self, pid: u32) -> Option<String> {
+        if self.side_bar.port_forwards.has_process(pid) {
+            return Some(self.context.clone());
+        }
+
+        self.context_caches
+            .iter()
+            .find(|(_, cache)| {
+                cache
+                    .side_bar
+                    .port_forwards
+                    .list
+                    .iter()
+                    .any(|item| item.pid == Some(pid))
+            })
+            .map(|(context, _)| context.clone())
+    }
+
+    /// Relaunches, all at once, the forwards of every context that were active when they were
+    /// last saved, if the user opted in. Only done on start, the forwards of a context switched
+    /// back to are still running.
+    fn restore_port_forwards(&mut self) {
+        if !self.restore_port_forwards {
+            return;
+        }
+
+        let contexts = std::iter::once(self.context.clone())
+            .chain(self.context_caches.keys().cloned())
+            .collect::<Vec<_>>();
+
+        for context in contexts {
+            let forwards = self
+                .with_port_forwards(context.as_str(), |port_forwards| {
+                    port_forwards.session_forwards()
+                })
+                .unwrap_or_default();
+
+            if !forwards.is_empty() {
+                self.restore_context_port_forwards(context, forwards);
+            }
+        }
+    }
+
+    fn restore_context_port_forwards(&mut self, context: String, forwards: Vec<PortForward>) {
+        let job = Job::RestorePortForwards {
+            context: context.clone(),
+        };
+
+        self.jobs.spawn(job, async move {
+            let launches = forwards.into_iter().map(|port_forward| async move {
+                // Proxies only bind local ports, they are started when the results are handled.
+                let result = if port_forward.proxied {
+                    Ok(port_forward.clone())
+                } else {
+                    launch_port_forward(port_forward.clone()).await
+                };
+
+                (port_forward, result)
+            });
+
+            AppEvent::PortForwardsRestored {
+                context,
+                results: join_all(launches).await,
+            }
+        });
+    }
+
+    fn switch_context(&mut self, new_context: String) {
+        if new_context != self.context {
+            let cache = self.context_cache();
+            let old_context = std::mem::replace(&mut self.context, new_context);
+            self.context_caches.insert(old_context, cache);
+
+            self.logs = None;
+            self.services = None;
+            self.deployments = None;
+            self.pod_details = None;
+            self.manifest = None;
+            self.close_edit();
+            self.jobs.cancel(&Job::Namespaces);
+            self.jobs.cancel(&Job::Services);
+            self.jobs.cancel(&Job::Deployments);
+            self.jobs.cancel(&Job::PodDetails);
+            self.jobs.cancel(&Job::Manifest);
+            // An in-flight restore is left to finish, its forwards are already being launched.
+
+            match self.context_caches.remove(&self.context) {
+                Some(cache) => self.restore_context_cache(cache),
+                None => {
+                    self.namespaces = NamespacesList::new(self.event_handler.sender());
+                    self.pods = None;
+                    self.side_bar = SideBar::new(self.event_handler.sender());
+                    self.load_namespaces();
+                }
+            }
+        }
+
+        self.active_window = ActiveWindow::Main(MainWindow::Namespaces);
+        self.main_window = MainWindow::Namespaces;
+    }
+
     fn draw(&mut self, frame: &mut Frame) {
+        self.draw_windows(frame);
+        self.notifications.draw(frame);
+    }
+
+    fn draw_windows(&mut self, frame: &mut Frame) {
+        if self.main_window == MainWindow::Edit {
+            match &mut self.edit {
+                Some(edit) => {
+                    return edit.draw(
+                        frame.area(),
+                        frame,
+                        self.active_window == ActiveWindow::Main(MainWindow::Edit),
+                    );
+                }
+                None => {
+                    self.main_window = MainWindow::Pods;
+                    self.active_window = ActiveWindow::Main(MainWindow::Pods);
+                }
+            }
+        }
+
+        if self.main_window == MainWindow::Manifest {
+            match &mut self.manifest {
+                Some(manifest) => {
+                    manifest.set_loading(self.jobs.is_running(&Job::Manifest));
+
+                    return manifest.draw(
+                        frame.area(),
+                        frame,
+                        self.active_window == ActiveWindow::Main(MainWindow::Manifest),
+                    );
+                }
+                None => {
+                    self.main_window = MainWindow::Pods;
+                    self.active_window = ActiveWindow::Main(MainWindow::Pods);
+                }
+            }
+        }
+
+        if self.main_window == MainWindow::Logs {
+            match &mut self.logs {
+                Some(logs) => {
+                    return logs.draw(
+                        frame.area(),
+                        frame,
+                        self.active_window == ActiveWindow::Main(MainWindow::Logs),
+                    );
+                }
+                None => {
+                    self.main_window = MainWindow::Pods;
+                    self.active_window = ActiveWindow::Main(MainWindow::Pods);
+                }
+            }
+        }
+
+        let [header_area, body_area] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(1)])
+            .areas(frame.area());
+
+        let header = Paragraph::new(format!(" ⎈ {}", self.context)).style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+
+        frame.render_widget(header, header_area);
+
         let layouts = Layout::default()
             .direction(Direction::Horizontal)
             .constraints(vec![Constraint::Percentage(25), Constraint::Percentage(75)])
-            .split(frame.area());
+            .split(body_area);
+
+        self.namespaces
+            .set_loading(self.jobs.is_running(&Job::Namespaces));
+        self.contexts
+            .set_loading(self.jobs.is_running(&Job::Contexts));
+
+        if let Some(services) = &mut self.services {
+            services.set_loading(self.jobs.is_running(&Job::Services));
+        }
+
+        if let Some(deployments) = &mut self.deployments {
+            deployments.set_loading(self.jobs.is_running(&Job::Deployments));
+        }
+
+        if let Some(pod_details) = &mut self.pod_details {
+            pod_details.set_loading(self.jobs.is_running(&Job::PodDetails));
+        }
+
+        self.side_bar
+            .port_forwards
+            .set_restore_on_start(self.restore_port_forwards);
+        self.side_bar
+            .port_forwards
+            .set_loading(self.jobs.any_running(|job| {
+                matches!(
+                    job,
+                    Job::PortForward { .. }
+                        | Job::ReplacePortForward { .. }
+                        | Job::AdoptPortForwards
+                ) || matches!(job, Job::RestorePortForwards { context } if *context == self.context)
+            }));
 
         let side_bar_focus = match self.active_window {
             ActiveWindow::SideBar(w) => Some(w),
@@ -103,14 +579,45 @@ impl App {
                 ),
                 None => self.main_window = MainWindow::Namespaces,
             },
+            MainWindow::Services => match &mut self.services {
+                Some(services) => services.draw(
+                    layouts[1],
+                    frame,
+                    self.active_window == ActiveWindow::Main(MainWindow::Services),
+                ),
+                None => self.main_window = MainWindow::Pods,
+            },
+            MainWindow::Deployments => match &mut self.deployments {
+                Some(deployments) => deployments.draw(
+                    layouts[1],
+                    frame,
+                    self.active_window == ActiveWindow::Main(MainWindow::Deployments),
+                ),
+                None => self.main_window = MainWindow::Pods,
+            },
+            MainWindow::PodDetails => match &mut self.pod_details {
+                Some(pod_details) => pod_details.draw(
+                    layouts[1],
+                    frame,
+                    self.active_window == ActiveWindow::Main(MainWindow::PodDetails),
+                ),
+                None => self.main_window = MainWindow::Pods,
+            },
+            MainWindow::Contexts => self.contexts.draw(
+                layouts[1],
+                frame,
+                self.active_window == ActiveWindow::Main(MainWindow::Contexts),
+            ),
+            MainWindow::Logs | MainWindow::Manifest | MainWindow::Edit => {}
         };
     }
 
-    async fn handle_events(&mut self) -> AppResult<()> {
-        match self.event_handler.next().await? {
+    async fn handle_event(&mut self, event: AppEvent) -> AppResult<()> {
+        match event {
+            AppEvent::Tick => self.probe_port_forwards(),
             AppEvent::Crossterm(crossterm_event) => match crossterm_event {
                 Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                    self.handle_key_event(key_event)
+                    self.handle_key_event(key_event)?
                 }
                 _ => {}
             },
@@ -125,49 +632,566 @@ impl App {
 
                 self.pods = Some(
                     PodsList::new(self.event_handler.sender())
-                        .load_by_namespace(new_namespace)
-                        .await?,
+                        .watch_namespace(self.context.clone(), new_namespace),
                 );
+                self.deployments = None;
 
                 self.active_window = ActiveWindow::Main(MainWindow::Pods);
                 self.main_window = MainWindow::Pods;
             }
-            AppEvent::PortForward {
-                pod_name,
-                local_port,
-                app_port,
+            AppEvent::SelectContext(new_context) => self.switch_context(new_context),
+            AppEvent::JobFinished { job, id, event } => {
+                if self.jobs.finish(&job, id) {
+                    self.event_handler.sender().send(*event);
+                }
+            }
+            AppEvent::CurrentContextLoaded(result) => {
+                self.context = result.context("Failed to get current context")?;
+                self.load_namespaces();
+            }
+            AppEvent::ContextsLoaded(result) => {
+                let contexts = result.context("Failed to load contexts")?;
+                self.contexts.update_list(contexts, self.context.as_str());
+            }
+            AppEvent::NamespacesLoaded(result) => {
+                let namespaces = result.context("Failed to download namespaces")?;
+                self.namespaces.update_list(namespaces);
+            }
+            AppEvent::PortForward(port_forward) if port_forward.proxied => {
+                self.side_bar.port_forwards.start_proxy(port_forward);
+            }
+            AppEvent::PortForward(port_forward) => {
+                self.side_bar.port_forwards.mark_starting(&port_forward);
+                self.spawn_port_forward(port_forward, Duration::ZERO);
+            }
+            AppEvent::AdoptPortForwards => {
+                let context = self.context.clone();
+
+                self.jobs.spawn(Job::AdoptPortForwards, async move {
+                    AppEvent::PortForwardsFound {
+                        result: find_outside_port_forwards(context.clone()).await,
+                        context,
+                    }
+                });
+            }
+            AppEvent::PortForwardsFound { context, result } => {
+                let found = result.context("Failed to look for port forwards")?;
+
+                let count = self
+                    .with_port_forwards(context.as_str(), |port_forwards| {
+                        port_forwards.adopt(found)
+                    })
+                    .unwrap_or_default();
+
+                let message = match count {
+                    0 => "No port forwards started outside the app were found".to_string(),
+                    count => format!("Adopted {count} port forwards started outside the app"),
+                };
+
+                self.notifications.push(Log::Info(message));
+            }
+            AppEvent::ReplacePortForward(port_forward) => {
+                self.side_bar.port_forwards.stop_conflicting(&port_forward);
+
+                // Stopping queues cancellations of the stopped forwards' jobs, so the new forward
+                // is started by a separate job once those went through and the ports are released.
+                // If they are not released in time, starting reports the port as in use.
+                let job = Job::ReplacePortForward {
+                    local_port: port_forward.local_port,
+                };
+
+                self.jobs.spawn(job, async move {
+                    kubectl::wait_for_ports_release(&port_forward.port_mappings()).await;
+                    AppEvent::PortForward(port_forward)
+                });
+            }
+            AppEvent::PortForwardStarted {
+                port_forward,
+                result,
+            } => {
+                // The context may have been switched while kubectl was starting.
+                let context = port_forward.context.clone();
+
+                if let Some((port_forward, delay)) = self
+                    .with_port_forwards(context.as_str(), |port_forwards| {
+                        port_forwards.handle_port_forward_started(*port_forward, result)
+                    })
+                    .flatten()
+                {
+                    self.spawn_port_forward(port_forward, delay);
+                }
+            }
+            AppEvent::PortForwardExited { pid } => {
+                // Forwards keep being supervised after their context is left.
+                let Some(context) = self.context_of_process(pid) else {
+                    return Ok(());
+                };
+
+                if let Some((port_forward, delay)) = self
+                    .with_port_forwards(context.as_str(), |port_forwards| {
+                        port_forwards.handle_port_forward_exited(pid)
+                    })
+                    .flatten()
+                {
+                    self.spawn_port_forward(port_forward, delay);
+                }
+            }
+            AppEvent::PortForwardProbed { probe, health } => {
+                // The context may have been switched while probing.
+                let context = probe.context.clone();
+
+                self.with_port_forwards(context.as_str(), |port_forwards| {
+                    port_forwards.handle_probe_result(probe, health)
+                });
+            }
+            AppEvent::PortForwardsRestored { context, results } => {
+                self.with_port_forwards(context.as_str(), |port_forwards| {
+                    port_forwards.handle_session_restored(results)
+                });
+            }
+            AppEvent::ToggleRestorePortForwards => {
+                self.restore_port_forwards = !self.restore_port_forwards;
+
+                let message = if self.restore_port_forwards {
+                    "Active port forwards will be restored on the next start"
+                } else {
+                    "Port forwards will not be restored on start"
+                };
+
+                self.notifications.push(Log::Info(message.to_string()));
+            }
+            AppEvent::CancelJob(job) => self.jobs.cancel(&job),
+            AppEvent::PodsWatch {
+                context,
                 namespace,
+                event,
             } => {
-                self.side_bar
-                    .port_forwards
-                    .add_to_list_and_port_forward(namespace, pod_name, local_port, app_port)
+                if let Some(pods) = &mut self.pods
+                    && pods.is_watching(context.as_str(), namespace.as_str())
+                {
+                    pods.handle_watch_event(event);
+                }
+            }
+            AppEvent::DeletePods {
+                context,
+                namespace,
+                names,
+                grace_period,
+                force,
+            } => {
+                self.jobs.spawn(Job::DeletePods(names.clone()), async move {
+                    let result = pods::delete_pods(
+                        context.as_str(),
+                        namespace.as_str(),
+                        &names,
+                        grace_period,
+                        force,
+                    )
                     .await;
+
+                    AppEvent::PodsDeleted {
+                        context,
+                        namespace,
+                        names,
+                        result,
+                    }
+                });
             }
+            AppEvent::PodsDeleted {
+                context,
+                namespace,
+                names,
+                result,
+            } => {
+                if result.is_err()
+                    && let Some(pods) = &mut self.pods
+                    && pods.is_watching(context.as_str(), namespace.as_str())
+                {
+                    pods.handle_delete_failed(&names);
+                }
+
+                result.context("Failed to delete pods")?;
 
+                let message = match names.as_slice() {
+                    [name] => format!("Deleting pod {name}"),
+                    names => format!("Deleting {} pods in {namespace}", names.len()),
+                };
+
+                self.notifications.push(Log::Info(message));
+            }
             AppEvent::ClosePodsList => {
                 self.active_window = ActiveWindow::Main(MainWindow::Namespaces);
                 self.pods = None;
+                self.deployments = None;
                 self.main_window = MainWindow::Namespaces;
             }
-            AppEvent::ShowNotification(log) => {
-                // TODO: Implement notifications
-                panic!("{:?}", log);
+            AppEvent::OpenServices { context, namespace } => {
+                self.services = Some(ServicesList::new(
+                    context.clone(),
+                    namespace.clone(),
+                    self.event_handler.sender(),
+                ));
+
+                self.jobs.spawn(Job::Services, async move {
+                    AppEvent::ServicesLoaded(
+                        services::get_services(context.as_str(), namespace.as_str()).await,
+                    )
+                });
+
+                self.active_window = ActiveWindow::Main(MainWindow::Services);
+                self.main_window = MainWindow::Services;
+            }
+            AppEvent::ServicesLoaded(result) => {
+                let list = result.context("Failed to load services")?;
+
+                if let Some(services) = &mut self.services {
+                    services.update_list(list);
+                }
+            }
+            AppEvent::CloseServices => {
+                self.jobs.cancel(&Job::Services);
+                self.services = None;
+                self.active_window = ActiveWindow::Main(MainWindow::Pods);
+                self.main_window = MainWindow::Pods;
+            }
+            AppEvent::OpenDeployments { context, namespace } => {
+                self.deployments = Some(DeploymentsList::new(
+                    context,
+                    namespace,
+                    self.event_handler.sender(),
+                ));
+
+                self.load_deployments();
+
+                self.active_window = ActiveWindow::Main(MainWindow::Deployments);
+                self.main_window = MainWindow::Deployments;
+            }
+            AppEvent::DeploymentsLoaded(result) => {
+                let list = result.context("Failed to load deployments")?;
+
+                if let Some(deployments) = &mut self.deployments {
+                    deployments.update_list(list);
+                }
+            }
+            AppEvent::ReloadDeployments => self.load_deployments(),
+            AppEvent::ScaleDeployment { name, replicas } => {
+                let Some((context, namespace)) = self.deployments.as_ref().map(|d| d.target())
+                else {
+                    return Ok(());
+                };
+
+                self.jobs
+                    .spawn(Job::UpdateDeployment(name.clone()), async move {
+                        let result = deployments::scale_deployment(
+                            context.as_str(),
+                            namespace.as_str(),
+                            name.as_str(),
+                            replicas,
+                        )
+                        .await;
+
+                        AppEvent::DeploymentUpdated { name, result }
+                    });
+            }
+            AppEvent::RestartDeployment(name) => {
+                let Some((context, namespace)) = self.deployments.as_ref().map(|d| d.target())
+                else {
+                    return Ok(());
+                };
+
+                self.jobs
+                    .spawn(Job::UpdateDeployment(name.clone()), async move {
+                        let result = deployments::restart_deployment(
+                            context.as_str(),
+                            namespace.as_str(),
+                            name.as_str(),
+                        )
+                        .await;
+
+                        AppEvent::DeploymentUpdated { name, result }
+                    });
+            }
+            AppEvent::DeploymentUpdated { name, result } => {
+                let summary =
+                    result.with_context(|| format!("Failed to update deployment {name}"))?;
+
+                self.notifications
+                    .push(Log::Info(summary.trim().to_string()));
+
+                if let Some(deployments) = &mut self.deployments {
+                    deployments.watch_rollout(name)?;
+                }
+
+                self.load_deployments();
+            }
+            AppEvent::RolloutStatus { rollout_id, event } => {
+                // Every step of the rollout changes the replica counts.
+                if let Some(deployments) = &mut self.deployments
+                    && deployments.handle_rollout_event(rollout_id, event)
+                {
+                    self.load_deployments();
+                }
+            }
+            AppEvent::ShowDeploymentPods { name, selector } => {
+                if let Some(pods) = &mut self.pods {
+                    pods.show_workload(Some((name, selector)));
+
+                    self.active_window = ActiveWindow::Main(MainWindow::Pods);
+                    self.main_window = MainWindow::Pods;
+                }
+            }
+            AppEvent::CloseDeploymentPods => {
+                if self.deployments.is_some() {
+                    self.active_window = ActiveWindow::Main(MainWindow::Deployments);
+                    self.main_window = MainWindow::Deployments;
+                }
+            }
+            AppEvent::CloseDeployments => {
+                self.jobs.cancel(&Job::Deployments);
+                self.deployments = None;
+                self.active_window = ActiveWindow::Main(MainWindow::Pods);
+                self.main_window = MainWindow::Pods;
+            }
+            AppEvent::OpenPodDetails {
+                context,
+                namespace,
+                pod_name,
+            } => {
+                self.pod_details = Some(PodDetailsView::new(
+                    context,
+                    namespace,
+                    pod_name,
+                    self.event_handler.sender(),
+                ));
+
+                self.load_pod_details();
+
+                self.active_window = ActiveWindow::Main(MainWindow::PodDetails);
+                self.main_window = MainWindow::PodDetails;
+            }
+            AppEvent::ReloadPodDetails => self.load_pod_details(),
+            AppEvent::PodDetailsLoaded(result) => {
+                let details = result.context("Failed to describe pod")?;
+
+                if let Some(pod_details) = &mut self.pod_details {
+                    pod_details.update(details);
+                }
+            }
+            AppEvent::ClosePodDetails => {
+                self.jobs.cancel(&Job::PodDetails);
+                self.pod_details = None;
+                self.active_window = ActiveWindow::Main(MainWindow::Pods);
+                self.main_window = MainWindow::Pods;
+            }
+            AppEvent::OpenManifest(resource) => {
+                self.manifest = Some(ManifestView::new(
+                    resource,
+                    self.main_window,
+                    self.event_handler.sender(),
+                ));
+
+                self.load_manifest(ManifestFormat::Yaml);
+
+                self.active_window = ActiveWindow::Main(MainWindow::Manifest);
+                self.main_window = MainWindow::Manifest;
+            }
+            AppEvent::LoadManifest(format) => self.load_manifest(format),
+            AppEvent::ManifestLoaded { format, result } => {
+                let manifest = result.context("Failed to load manifest")?;
+
+                if let Some(view) = &mut self.manifest {
+                    view.update(format, manifest);
+                }
+            }
+            AppEvent::CloseManifest => {
+                self.jobs.cancel(&Job::Manifest);
+
+                let opened_from = self
+                    .manifest
+                    .take()
+                    .map(|view| view.opened_from())
+                    .unwrap_or(MainWindow::Pods);
+
+                self.active_window = ActiveWindow::Main(opened_from);
+                self.main_window = opened_from;
+            }
+            AppEvent::EditResource(resource) => {
+                self.jobs.spawn(Job::Edit, async move {
+                    AppEvent::EditorReady(EditSession::start(resource).await)
+                });
+            }
+            AppEvent::EditorReady(result) => {
+                let session = result.context("Failed to fetch the resource for editing")?;
+                self.open_editor(session);
+            }
+            AppEvent::ReopenEditor => {
+                if let Some(edit) = &self.edit {
+                    self.open_editor(edit.session().clone());
+                }
+            }
+            AppEvent::EditorClosed(session) => {
+                if !session.is_changed().await? {
+                    self.notifications.push(Log::Info(format!(
+                        "No changes made to {}",
+                        session.resource.target()
+                    )));
+
+                    session.discard();
+                    self.close_edit();
+                    return Ok(());
+                }
+
+                let edit = self.edit.get_or_insert_with(|| {
+                    EditView::new(
+                        session.clone(),
+                        self.main_window,
+                        self.event_handler.sender(),
+                    )
+                });
+
+                edit.set_diffing();
+
+                self.jobs.spawn(Job::Edit, async move {
+                    let context = session.resource.context.as_str();
+                    AppEvent::EditDiffLoaded(diff_manifest(context, &session.path).await)
+                });
+
+                self.active_window = ActiveWindow::Main(MainWindow::Edit);
+                self.main_window = MainWindow::Edit;
+            }
+            AppEvent::EditDiffLoaded(result) => {
+                if let Some(edit) = &mut self.edit {
+                    edit.handle_diff(result);
+                }
+            }
+            AppEvent::ApplyEdit => {
+                if let Some(edit) = &mut self.edit {
+                    edit.set_applying();
+
+                    let session = edit.session().clone();
+
+                    self.jobs.spawn(Job::Edit, async move {
+                        let context = session.resource.context.as_str();
+                        AppEvent::EditApplied(apply_manifest(context, &session.path).await)
+                    });
+                }
+            }
+            AppEvent::EditApplied(result) => match result {
+                Ok(summary) => {
+                    let target = self
+                        .edit
+                        .as_ref()
+                        .map(|edit| edit.session().resource.target());
+                    let summary = match summary.trim() {
+                        "" => format!("Applied {}", target.unwrap_or_default()),
+                        summary => summary.to_string(),
+                    };
+
+                    self.notifications.push(Log::Info(summary));
+                    self.close_edit();
+
+                    if let Some(manifest) = &self.manifest {
+                        self.load_manifest(manifest.format());
+                    }
+                }
+                Err(err) => {
+                    if let Some(edit) = &mut self.edit {
+                        edit.handle_apply_failed(err);
+                    }
+                }
+            },
+            AppEvent::CloseEdit => self.close_edit(),
+            AppEvent::OpenLogs(source) => {
+                self.logs = Some(LogsView::new(source, self.event_handler.sender()).start()?);
+
+                self.active_window = ActiveWindow::Main(MainWindow::Logs);
+                self.main_window = MainWindow::Logs;
+            }
+            AppEvent::LogsStream { stream_id, event } => {
+                if let Some(logs) = &mut self.logs {
+                    logs.handle_stream_event(stream_id, event);
+                }
+            }
+            AppEvent::CloseLogs => {
+                let logs = self.logs.take();
+
+                // Return to where the logs were opened from.
+                self.active_window = match logs.as_ref().map(LogsView::source) {
+                    Some(LogsSource::PortForward { .. }) => {
+                        ActiveWindow::SideBar(SideBarWindow::RecentPortForwards)
+                    }
+                    _ => ActiveWindow::Main(MainWindow::Pods),
+                };
+
+                self.main_window = MainWindow::Pods;
+            }
+            AppEvent::RunInteractive(command) => self.interactive_command = Some(command),
+            AppEvent::ShowNotification(log) => self.notifications.push(log),
+            AppEvent::ToggleNotificationsHistory => self.notifications.toggle_history(),
+            AppEvent::Focus(active_window) => {
+                if active_window == ActiveWindow::Main(MainWindow::Contexts) {
+                    self.load_contexts();
+                } else {
+                    self.jobs.cancel(&Job::Contexts);
+                }
+
+                if let ActiveWindow::Main(main_window) = active_window {
+                    self.main_window = main_window;
+                }
+
+                self.active_window = active_window;
             }
-            AppEvent::Focus(active_window) => self.active_window = active_window,
         }
 
         Ok(())
     }
 
-    fn handle_key_event(&mut self, key: KeyEvent) {
+    fn handle_key_event(&mut self, key: KeyEvent) -> AppResult<()> {
+        if self.notifications.is_history_open() {
+            self.notifications.handle_key_event(key);
+            return Ok(());
+        }
+
         match &self.active_window {
             ActiveWindow::Main(main) => match main {
                 MainWindow::Namespaces => self.namespaces.handle_key_event(key),
                 MainWindow::Pods => {
                     if let Some(pods) = &mut self.pods {
-                        pods.handle_key_event(key)
+                        pods.handle_key_event(key, &self.side_bar.port_forwards.forwarded_ports())
+                    }
+                }
+                MainWindow::Services => {
+                    if let Some(services) = &mut self.services {
+                        services
+                            .handle_key_event(key, &self.side_bar.port_forwards.forwarded_ports())
                     }
                 }
+                MainWindow::Deployments => {
+                    if let Some(deployments) = &mut self.deployments {
+                        deployments.handle_key_event(key)
+                    }
+                }
+                MainWindow::PodDetails => {
+                    if let Some(pod_details) = &mut self.pod_details {
+                        pod_details.handle_key_event(key)
+                    }
+                }
+                MainWindow::Manifest => {
+                    if let Some(manifest) = &mut self.manifest {
+                        manifest.handle_key_event(key)
+                    }
+                }
+                MainWindow::Edit => {
+                    if let Some(edit) = &mut self.edit {
+                        edit.handle_key_event(key)
+                    }
+                }
+                MainWindow::Logs => {
+                    if let Some(logs) = &mut self.logs {
+                        logs.handle_key_event(key)?;
+                    }
+                }
+                MainWindow::Contexts => self.contexts.handle_key_event(key),
             },
             ActiveWindow::SideBar(side_bar) => match side_bar {
                 SideBarWindow::RecentNamespaces => {
@@ -176,24 +1200,60 @@ impl App {
                 SideBarWindow::RecentPortForwards => {
                     self.side_bar.port_forwards.handle_key_event(key)
                 }
+                SideBarWindow::PortForwardProfiles => {
+                    self.side_bar.port_forwards.handle_profiles_key_event(key)
+                }
             },
         }
+
+        Ok(())
     }
 
-    fn merge_cache(&mut self, cache: AppCache) {
+    /// Returns `false` when the cache has no state for its current context.
+    fn merge_cache(&mut self, mut cache: AppCache) -> bool {
         self.active_window = cache.active_window;
         self.main_window = cache.main_window;
+        self.context = cache.context;
+        self.restore_port_forwards = cache.restore_port_forwards;
 
-        self.namespaces
-            .restore_from_cache(cache.namespaces.namespace_list.into());
+        // The contexts, services, deployments, pod details and manifests are not cached, they are
+        // empty right after start.
+        if matches!(
+            self.main_window,
+            MainWindow::Contexts
+                | MainWindow::Services
+                | MainWindow::Deployments
+                | MainWindow::PodDetails
+                | MainWindow::Manifest
+                | MainWindow::Edit
+        ) {
+            self.main_window = MainWindow::Namespaces;
+            self.active_window = ActiveWindow::Main(MainWindow::Namespaces);
+        }
 
-        self.pods = cache
-            .pods
-            .map(|pods_cache| PodsList::from_cache(pods_cache, self.event_handler.sender()));
+        let context_cache = cache.contexts.remove(&self.context);
+        self.context_caches = cache.contexts;
+
+        // Only the processes still running are trusted, and monitored, in the contexts that are
+        // not shown as well.
+        let event_sender = self.event_handler.sender();
+
+        for cache in self.context_caches.values_mut() {
+            let port_forwards = cache.side_bar.port_forwards.clone();
+            cache.side_bar.port_forwards =
+                PortForwardsList::from_cache(port_forwards, event_sender.clone()).into();
+        }
+
+        let is_restored = match context_cache {
+            Some(context_cache) => {
+                self.restore_context_cache(context_cache);
+                true
+            }
+            None => false,
+        };
 
-        self.side_bar = self
-            .side_bar
-            .from_cache(cache.side_bar, self.event_handler.sender());
+        self.restore_port_forwards();
+        is_restored
     }
 }
 
@@ -202,13 +1262,28 @@ impl Default for App {
         let event_handler = EventHandler::new();
 
         Self {
+            context: String::new(),
+            context_caches: HashMap::new(),
+            contexts: ContextsList::new(event_handler.sender()),
             main_window: MainWindow::Namespaces,
             active_window: ActiveWindow::Main(MainWindow::Namespaces),
             namespaces: NamespacesList::new(event_handler.sender()),
+            notifications: Notifications::default(),
+            jobs: JobRunner::new(event_handler.sender()),
             side_bar: SideBar::new(event_handler.sender()),
             exit: false,
             event_handler,
             pods: None,
+            services: None,
+            deployments: None,
+            pod_details: None,
+            logs: None,
+            manifest: None,
+            edit: None,
+            interactive_command: None,
+            after_interactive: None,
+            restore_port_forwards: false,
+            cache_lock: None,
         }
     }
 }
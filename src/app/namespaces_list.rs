@@ -27,6 +27,10 @@ impl NamespacesList {
         }
     }
 
+    pub fn set_loading(&mut self, is_loading: bool) {
+        self.namespace_list.is_loading = is_loading;
+    }
+
     pub fn draw(&mut self, area: Rect, frame: &mut Frame, is_focused: bool) {
         self.namespace_list.draw(area, frame, is_focused);
     }
@@ -40,6 +44,8 @@ impl NamespacesList {
     }
 
     pub fn handle_key_event(&mut self, key: KeyEvent) {
+        let is_filtering = self.namespace_list.is_filtering();
+
         if let Some(list_event) = self.namespace_list.handle_key(key) {
             match list_event {
                 ListEvent::Quit => {
@@ -51,6 +57,8 @@ impl NamespacesList {
             };
         }
 
-        handle_general_keys(key, &self.event_sender);
+        if !is_filtering {
+            handle_general_keys(key, &self.event_sender);
+        }
     }
 }
@@ -0,0 +1,211 @@
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::app::{
+    common::{build_block, centered_rect},
+    events::Log,
+};
+
+const TOAST_WIDTH: u16 = 50;
+const MAX_TOASTS: usize = 3;
+const MAX_TOAST_LINES: usize = 4;
+const MAX_HISTORY: usize = 200;
+
+impl Log {
+    fn message(&self) -> &str {
+        match self {
+            Log::Info(message) | Log::Warning(message) | Log::Error(message) => message,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Log::Info(_) => "Info",
+            Log::Warning(_) => "Warning",
+            Log::Error(_) => "Error",
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Log::Info(_) => Color::Green,
+            Log::Warning(_) => Color::Yellow,
+            Log::Error(_) => Color::Red,
+        }
+    }
+
+    fn toast_duration(&self) -> Duration {
+        match self {
+            Log::Info(_) => Duration::from_secs(3),
+            Log::Warning(_) => Duration::from_secs(6),
+            Log::Error(_) => Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Notification {
+    log: Log,
+    created_at: Instant,
+}
+
+/// Color coded toasts in the top right corner plus a history of everything that was shown,
+/// so long kubectl errors can be read in full.
+#[derive(Debug, Default)]
+pub struct Notifications {
+    history: Vec<Notification>,
+    is_history_open: bool,
+    scroll: u16,
+}
+
+impl Notifications {
+    pub fn push(&mut self, log: Log) {
+        self.history.push(Notification {
+            log,
+            created_at: Instant::now(),
+        });
+
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+    }
+
+    pub fn is_history_open(&self) -> bool {
+        self.is_history_open
+    }
+
+    pub fn toggle_history(&mut self) {
+        self.is_history_open = !self.is_history_open;
+        self.scroll = 0;
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame) {
+        if self.is_history_open {
+            self.draw_history(frame);
+            return;
+        }
+
+        self.draw_toasts(frame);
+    }
+
+    fn draw_toasts(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let width = TOAST_WIDTH.min(area.width);
+        let mut y = area.y + 1;
+
+        let toasts = self
+            .history
+            .iter()
+            .rev()
+            .filter(|n| n.created_at.elapsed() < n.log.toast_duration())
+            .take(MAX_TOASTS);
+
+        for notification in toasts {
+            let inner_width = width.saturating_sub(2).max(1) as usize;
+
+            let lines = notification
+                .log
+                .message()
+                .lines()
+                .map(|line| line.chars().count().div_ceil(inner_width).max(1))
+                .sum::<usize>()
+                .clamp(1, MAX_TOAST_LINES) as u16;
+
+            let height = lines + 2;
+
+            if y + height > area.bottom() {
+                break;
+            }
+
+            let toast_area = Rect::new(area.right().saturating_sub(width + 1), y, width, height);
+            y += height;
+
+            let block = Block::default()
+                .title(notification.log.label())
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(notification.log.color()));
+
+            let toast = Paragraph::new(notification.log.message())
+                .wrap(Wrap { trim: false })
+                .block(block);
+
+            frame.render_widget(Clear, toast_area);
+            frame.render_widget(toast, toast_area);
+        }
+    }
+
+    fn draw_history(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let area = centered_rect(
+            area,
+            area.width.saturating_mul(4) / 5,
+            area.height.saturating_mul(4) / 5,
+        );
+
+        let mut lines = vec![];
+
+        for notification in self.history.iter().rev() {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    notification.log.label(),
+                    Style::default()
+                        .fg(notification.log.color())
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(
+                    " {}s ago",
+                    notification.created_at.elapsed().as_secs()
+                )),
+            ]));
+
+            lines.extend(
+                notification
+                    .log
+                    .message()
+                    .lines()
+                    .map(|line| Line::from(format!("  {line}"))),
+            );
+
+            lines.push(Line::default());
+        }
+
+        if lines.is_empty() {
+            lines.push(Line::from("No notifications"));
+        }
+
+        let block = build_block("Notifications (j/k scroll, c clear, Esc close)", true)
+            .title_alignment(Alignment::Center);
+
+        let history = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0))
+            .block(block);
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(history, area);
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::Char('c') => {
+                self.history.clear();
+                self.scroll = 0;
+            }
+            KeyCode::Esc | KeyCode::Char('n') => self.toggle_history(),
+            _ => {}
+        }
+    }
+}
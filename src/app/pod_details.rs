@@ -0,0 +1,380 @@
+use std::collections::BTreeMap;
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    app::{
+        common::{build_block, loading_title},
+        events::{AppEvent, EventSender},
+    },
+    kubectl::pods::{ContainerDetails, PodDetails},
+};
+
+/// Scrollable `kubectl describe` like view of a single pod.
+pub struct PodDetailsView {
+    context: String,
+    namespace: String,
+    pod_name: String,
+    lines: Vec<Line<'static>>,
+    scroll: usize,
+    page_height: usize,
+    is_loading: bool,
+    event_sender: EventSender,
+}
+
+impl PodDetailsView {
+    pub fn new(
+        context: String,
+        namespace: String,
+        pod_name: String,
+        event_sender: EventSender,
+    ) -> Self {
+        Self {
+            context,
+            namespace,
+            pod_name,
+            lines: vec![],
+            scroll: 0,
+            page_height: 0,
+            is_loading: true,
+            event_sender,
+        }
+    }
+
+    /// Context, namespace and name of the described pod.
+    pub fn target(&self) -> (String, String, String) {
+        (
+            self.context.clone(),
+            self.namespace.clone(),
+            self.pod_name.clone(),
+        )
+    }
+
+    pub fn set_loading(&mut self, is_loading: bool) {
+        self.is_loading = is_loading;
+    }
+
+    /// Replaces the shown details, keeping the scroll position across reloads.
+    pub fn update(&mut self, details: PodDetails) {
+        self.lines = render_details(&details);
+        self.scroll = self.scroll.min(self.max_scroll());
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.lines.len().saturating_sub(self.page_height)
+    }
+
+    pub fn draw(&mut self, area: Rect, frame: &mut Frame, is_focused: bool) {
+        let layouts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Min(3), Constraint::Length(3)])
+            .split(area);
+
+        // Borders take two lines.
+        self.page_height = layouts[0].height.saturating_sub(2) as usize;
+        self.scroll = self.scroll.min(self.max_scroll());
+
+        let lines: Vec<Line> = self
+            .lines
+            .iter()
+            .skip(self.scroll)
+            .take(self.page_height)
+            .cloned()
+            .collect();
+
+        let title = loading_title(
+            format!("Pod {}/{}", self.namespace, self.pod_name).as_str(),
+            self.is_loading,
+        );
+
+        frame.render_widget(
+            Paragraph::new(lines).block(build_block(title.as_str(), is_focused)),
+            layouts[0],
+        );
+
+        frame.render_widget(
+            Paragraph::new("j/k scroll | g/G top/bottom | r reload | Esc close")
+                .block(build_block("Keys", false)),
+            layouts[1],
+        );
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('q') => self.event_sender.send(AppEvent::Quit),
+            KeyCode::Esc => self.event_sender.send(AppEvent::ClosePodDetails),
+            KeyCode::Char('r') => self.event_sender.send(AppEvent::ReloadPodDetails),
+            KeyCode::Char('j') | KeyCode::Down => self.scroll_down(1),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll_up(1),
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.scroll_down(self.page_height / 2)
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.scroll_up(self.page_height / 2)
+            }
+            KeyCode::PageDown => self.scroll_down(self.page_height),
+            KeyCode::PageUp => self.scroll_up(self.page_height),
+            KeyCode::Char('g') | KeyCode::Home => self.scroll = 0,
+            KeyCode::Char('G') | KeyCode::End => self.scroll = self.max_scroll(),
+            _ => {}
+        }
+    }
+
+    fn scroll_down(&mut self, lines: usize) {
+        self.scroll = (self.scroll + lines).min(self.max_scroll());
+    }
+
+    fn scroll_up(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+}
+
+fn render_details(details: &PodDetails) -> Vec<Line<'static>> {
+    let mut lines = vec![];
+
+    section(&mut lines, "Metadata");
+    field(&mut lines, 1, "Name", details.name.clone());
+    field(&mut lines, 1, "Namespace", details.namespace.clone());
+    optional_field(&mut lines, 1, "Created", &details.created_at);
+    optional_field(&mut lines, 1, "Started", &details.started_at);
+    field(&mut lines, 1, "Phase", details.phase.clone());
+
+    if !details.owners.is_empty() {
+        field(&mut lines, 1, "Controlled by", details.owners.join(", "));
+    }
+
+    map(&mut lines, "Labels", &details.labels);
+    map(&mut lines, "Annotations", &details.annotations);
+
+    section(&mut lines, "Placement");
+    optional_field(&mut lines, 1, "Node", &details.node);
+    optional_field(&mut lines, 1, "Host IP", &details.host_ip);
+
+    match details.pod_ips.as_slice() {
+        [] => field(&mut lines, 1, "Pod IPs", "<none>".to_string()),
+        pod_ips => field(&mut lines, 1, "Pod IPs", pod_ips.join(", ")),
+    }
+
+    optional_field(&mut lines, 1, "QoS class", &details.qos_class);
+
+    section(&mut lines, "Conditions");
+
+    if details.conditions.is_empty() {
+        lines.push(indented(1, "<none>"));
+    }
+
+    for condition in &details.conditions {
+        let mut spans = vec![
+            Span::raw(format!("  {:<28}", condition.kind)),
+            Span::styled(
+                format!("{:<8}", condition.status),
+                condition_style(condition.status.as_str()),
+            ),
+        ];
+
+        if let Some(time) = &condition.last_transition_time {
+            spans.push(Span::styled(
+                format!("since {time}"),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+
+        lines.push(Line::from(spans));
+
+        let reason = match (&condition.reason, &condition.message) {
+            (Some(reason), Some(message)) => Some(format!("{reason}: {message}")),
+            (reason, message) => reason.clone().or(message.clone()),
+        };
+
+        if let Some(reason) = reason {
+            lines.push(indented(2, reason.as_str()));
+        }
+    }
+
+    if !details.init_containers.is_empty() {
+        section(&mut lines, "Init containers");
+
+        for container in &details.init_containers {
+            render_container(&mut lines, container);
+        }
+    }
+
+    section(&mut lines, "Containers");
+
+    for container in &details.containers {
+        render_container(&mut lines, container);
+    }
+
+    section(&mut lines, "Volumes");
+
+    if details.volumes.is_empty() {
+        lines.push(indented(1, "<none>"));
+    }
+
+    for (name, source) in &details.volumes {
+        field(&mut lines, 1, name, source.clone());
+    }
+
+    section(&mut lines, "Events");
+
+    match &details.events {
+        Ok(events) if events.is_empty() => lines.push(indented(1, "<none>")),
+        Ok(events) => {
+            for event in events {
+                let style = match event.kind.as_str() {
+                    "Warning" => Style::default().fg(Color::Yellow),
+                    _ => Style::default(),
+                };
+
+                let count = match event.count {
+                    1 => String::new(),
+                    count => format!(" (x{count})"),
+                };
+
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("  {} ", event.last_seen.as_deref().unwrap_or("-")),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(format!("{:<8}", event.kind), style),
+                    Span::styled(format!("{}{count}: ", event.reason), style),
+                    Span::raw(event.message.clone()),
+                ]));
+            }
+        }
+        Err(err) => lines.push(Line::styled(
+            format!(
+                "  Failed to load events: {}",
+                err.lines().next().unwrap_or_default()
+            ),
+            Style::default().fg(Color::Red),
+        )),
+    }
+
+    lines
+}
+
+fn render_container(lines: &mut Vec<Line<'static>>, container: &ContainerDetails) {
+    lines.push(Line::styled(
+        format!("  {}", container.name),
+        Style::default().add_modifier(Modifier::BOLD),
+    ));
+
+    field(lines, 2, "Image", container.image.clone());
+
+    if !container.ports.is_empty() {
+        let ports: Vec<String> = container.ports.iter().map(|port| port.label()).collect();
+        field(lines, 2, "Ports", ports.join(", "));
+    }
+
+    let state = container
+        .state
+        .as_ref()
+        .map(|state| state.describe())
+        .unwrap_or("Not created".to_string());
+
+    field(lines, 2, "State", state);
+
+    if let Some(last_state) = &container.last_state {
+        let last_state = last_state.describe();
+
+        if last_state != "None" {
+            field(lines, 2, "Last state", last_state);
+        }
+    }
+
+    field(lines, 2, "Ready", container.ready.to_string());
+    field(lines, 2, "Restarts", container.restart_count.to_string());
+
+    if !container.requests.is_empty() {
+        field(lines, 2, "Requests", resources(&container.requests));
+    }
+
+    if !container.limits.is_empty() {
+        field(lines, 2, "Limits", resources(&container.limits));
+    }
+
+    for (kind, probe) in &container.probes {
+        field(lines, 2, format!("{kind} probe").as_str(), probe.clone());
+    }
+}
+
+/// `cpu=100m, memory=128Mi`
+fn resources(resources: &BTreeMap<String, String>) -> String {
+    resources
+        .iter()
+        .map(|(name, quantity)| format!("{name}={quantity}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn section(lines: &mut Vec<Line<'static>>, title: &str) {
+    if !lines.is_empty() {
+        lines.push(Line::default());
+    }
+
+    lines.push(Line::styled(
+        title.to_string(),
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    ));
+}
+
+fn field(lines: &mut Vec<Line<'static>>, depth: usize, name: &str, value: String) {
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!("{}{name}: ", "  ".repeat(depth)),
+            Style::default().fg(Color::Blue),
+        ),
+        Span::raw(value),
+    ]));
+}
+
+fn optional_field(
+    lines: &mut Vec<Line<'static>>,
+    depth: usize,
+    name: &str,
+    value: &Option<String>,
+) {
+    field(
+        lines,
+        depth,
+        name,
+        value.clone().unwrap_or("<none>".to_string()),
+    );
+}
+
+fn map(lines: &mut Vec<Line<'static>>, name: &str, values: &BTreeMap<String, String>) {
+    if values.is_empty() {
+        field(lines, 1, name, "<none>".to_string());
+        return;
+    }
+
+    field(lines, 1, name, String::new());
+
+    for (key, value) in values {
+        // Values like `last-applied-configuration` span many lines.
+        let value = value.lines().next().unwrap_or_default();
+        lines.push(indented(2, format!("{key}={value}").as_str()));
+    }
+}
+
+fn indented(depth: usize, text: &str) -> Line<'static> {
+    Line::from(format!("{}{text}", "  ".repeat(depth)))
+}
+
+fn condition_style(status: &str) -> Style {
+    match status {
+        "True" => Style::default().fg(Color::Green),
+        "False" => Style::default().fg(Color::Red),
+        _ => Style::default().fg(Color::Yellow),
+    }
+}
@@ -0,0 +1,55 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+};
+
+use crate::{
+    app::common::{FilterableList, ListEvent, centered_rect},
+    kubectl::pods::PodContainer,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerAction {
+    Logs,
+    Exec,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContainerPopup {
+    pub action: ContainerAction,
+    pod_containers_list: FilterableList<PodContainer>,
+}
+
+pub enum ContainerPopupAction {
+    Selected(PodContainer),
+    Quit,
+}
+
+impl ContainerPopup {
+    pub fn new(action: ContainerAction, pod_containers: Vec<PodContainer>) -> Self {
+        let mut list = FilterableList::new("Select container".to_string(), false);
+        list.set_items(pod_containers);
+
+        Self {
+            action,
+            pod_containers_list: list,
+        }
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let height = self.pod_containers_list.list.len() as u16 + 3;
+        let area = centered_rect(frame.area(), 30, height);
+        self.pod_containers_list.draw(area, frame, true);
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) -> Option<ContainerPopupAction> {
+        if key.code == KeyCode::Esc {
+            return Some(ContainerPopupAction::Quit);
+        }
+
+        match self.pod_containers_list.handle_key(key)? {
+            ListEvent::Quit => Some(ContainerPopupAction::Quit),
+            ListEvent::SelectedItem(item) => Some(ContainerPopupAction::Selected(item)),
+        }
+    }
+}
@@ -1,4 +1,9 @@
-mod port_forward_popup;
+mod container_popup;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Instant,
+};
 
 use crossterm::event::KeyCode;
 
@@ -6,18 +11,29 @@ use ratatui::{
     Frame,
     crossterm::event::KeyEvent,
     layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
     widgets::{Cell, Paragraph, Row, Table, TableState},
 };
 
 use crate::{
     app::{
         cache::{PodsListCache, StateCache},
-        common::{build_block, get_highlight_style, handle_general_keys},
-        events::{AppEvent, EventSender},
-        pods_list::port_forward_popup::{PortForwardPopup, PortForwardPopupAction},
+        common::{
+            ConfirmAction, ConfirmDialog, ForwardedPorts, OptionValue, PortChoice,
+            PortForwardPopup, PortForwardPopupAction, build_block, get_highlight_style,
+            handle_general_keys, loading_title,
+        },
+        events::{AppEvent, EventSender, Log},
+        logs_view::LogsSource,
+        pods_list::container_popup::{ContainerAction, ContainerPopup, ContainerPopupAction},
+        side_bar::port_forwards::PortForward,
+    },
+    kubectl::{
+        KubectlStream, TargetKind,
+        exec::exec_shell_command,
+        manifest::ResourceRef,
+        pods::{KnownPodStatus, Pod, PodStatus, PodsWatchEvent, watch_pods},
     },
-    error::AppResult,
-    kubectl::pods::{KnownPodStatus, Pod, PodStatus, get_pods_list},
 };
 
 #[derive(Debug, Clone)]
@@ -30,7 +46,20 @@ pub struct PodsList {
     is_filter_mod: bool,
     longest_name: u16,
     port_forward_popup: Option<PortForwardPopup>,
+    container_popup: Option<ContainerPopup>,
+    delete_dialog: Option<ConfirmDialog>,
+    /// Pods marked with space for a bulk delete.
+    marked: BTreeSet<String>,
+    /// Pods we asked to delete, shown as terminating before the watch reports it.
+    terminating: BTreeSet<String>,
+    /// Deployment whose pods are shown, with its selector, when opened from the deployments.
+    workload: Option<(String, BTreeMap<String, String>)>,
     namespace: String,
+    context: String,
+    watch: Option<KubectlStream>,
+    last_update: Option<Instant>,
+    watch_status: Option<String>,
+    is_polling: bool,
 }
 
 impl From<PodsList> for PodsListCache {
@@ -42,6 +71,7 @@ impl From<PodsList> for PodsListCache {
             original_list: value.original_list,
             longest_name: value.longest_name,
             namespace: value.namespace,
+            context: value.context,
             state: StateCache {
                 selected: value.state.selected(),
             },
@@ -63,25 +93,165 @@ impl PodsList {
             original_list: value.original_list,
             longest_name: value.longest_name,
             namespace: value.namespace,
+            context: value.context,
             state,
             port_forward_popup: value.port_forward_popup.map(|i| i.into()),
+            container_popup: None,
+            delete_dialog: None,
+            marked: BTreeSet::new(),
+            terminating: BTreeSet::new(),
+            workload: None,
+            watch: None,
+            last_update: None,
+            watch_status: None,
+            is_polling: false,
         }
+        .start_watch()
+    }
+
+    pub fn watch_namespace(mut self, context: String, namespace: String) -> Self {
+        self.context = context;
+        self.namespace = namespace;
+
+        self.start_watch()
+    }
+
+    fn start_watch(mut self) -> Self {
+        let event_sender = self.event_sender.clone();
+        let context = self.context.clone();
+        let namespace = self.namespace.clone();
+
+        self.watch = Some(watch_pods(
+            self.context.as_str(),
+            self.namespace.as_str(),
+            move |event| {
+                event_sender.send(AppEvent::PodsWatch {
+                    context: context.clone(),
+                    namespace: namespace.clone(),
+                    event,
+                })
+            },
+        ));
+
+        self
     }
 
-    pub async fn load_by_namespace(mut self, namespace: String) -> AppResult<Self> {
-        let pods = get_pods_list(namespace.as_str()).await?;
+    pub fn is_watching(&self, context: &str, namespace: &str) -> bool {
+        self.context == context && self.namespace == namespace
+    }
+
+    pub fn handle_watch_event(&mut self, event: PodsWatchEvent) {
+        let selected_name = self.selected_pod().map(|pod| pod.name.clone());
+
+        match event {
+            PodsWatchEvent::Snapshot(pods) => self.original_list = pods,
+            PodsWatchEvent::Applied(pod) => {
+                match self.original_list.iter().position(|p| p.name == pod.name) {
+                    Some(index) => self.original_list[index] = pod,
+                    None => {
+                        let index = self
+                            .original_list
+                            .partition_point(|p| p.name.as_str() < pod.name.as_str());
+
+                        self.original_list.insert(index, pod);
+                    }
+                }
+            }
+            PodsWatchEvent::Deleted(name) => self.original_list.retain(|p| p.name != name),
+            PodsWatchEvent::Disconnected(reason) => {
+                self.watch_status = Some(format!("reconnecting: {reason}"));
+                return;
+            }
+            PodsWatchEvent::Polling => {
+                self.is_polling = true;
+                return;
+            }
+            PodsWatchEvent::Watching => {
+                self.is_polling = false;
+                return;
+            }
+        }
+
+        self.watch_status = None;
+        self.last_update = Some(Instant::now());
+
+        let names: BTreeSet<&String> = self.original_list.iter().map(|p| &p.name).collect();
+        self.marked.retain(|name| names.contains(name));
+        self.terminating.retain(|name| names.contains(name));
 
-        let longest_name = pods
+        self.longest_name = self
+            .original_list
             .iter()
-            .max_by_key(|p| p.name.len())
             .map(|p| p.name.len())
+            .max()
             .unwrap_or(10) as u16;
 
-        self.longest_name = longest_name;
-        self.original_list = pods.clone();
-        self.filtered_list = pods;
+        self.update_filtered_list();
+
+        let selected_index =
+            selected_name.and_then(|name| self.filtered_list.iter().position(|p| p.name == name));
+
+        if let Some(index) = selected_index {
+            self.state.select(Some(index));
+        } else if let Some(selected) = self.state.selected()
+            && selected >= self.filtered_list.len()
+        {
+            self.state
+                .select(Some(self.filtered_list.len().saturating_sub(1)));
+        }
+    }
+
+    fn update_filtered_list(&mut self) {
+        self.filtered_list = self
+            .original_list
+            .iter()
+            .filter(|item| {
+                if let Some((_, selector)) = &self.workload
+                    && !item.matches_selector(selector)
+                {
+                    return false;
+                }
+
+                if self.filter.is_empty() {
+                    return true;
+                }
 
-        Ok(self)
+                item.name.contains(&self.filter)
+            })
+            .map(|item| item.to_owned())
+            .collect();
+    }
+
+    /// Limits the list to the pods of a deployment, or shows every pod again with `None`.
+    pub fn show_workload(&mut self, workload: Option<(String, BTreeMap<String, String>)>) {
+        self.workload = workload;
+        self.update_filtered_list();
+        self.state.select(Some(0));
+    }
+
+    fn watch_title(&self) -> String {
+        let label = match &self.workload {
+            Some((deployment, _)) => format!("Pods of deployment {deployment}"),
+            None => "Select pod".to_string(),
+        };
+
+        if let Some(status) = &self.watch_status {
+            return format!("{label} ({status})");
+        }
+
+        let mode = if self.is_polling {
+            "polling"
+        } else {
+            "watching"
+        };
+
+        match self.last_update {
+            Some(last_update) => format!(
+                "{label} ({mode}, updated {}s ago)",
+                last_update.elapsed().as_secs()
+            ),
+            None => loading_title(label.as_str(), true),
+        }
     }
 
     pub fn new(event_sender: EventSender) -> Self {
@@ -91,6 +261,7 @@ impl PodsList {
         Self {
             filtered_list: Vec::new(),
             namespace: String::new(),
+            context: String::new(),
             longest_name: 0,
             original_list: Vec::new(),
             event_sender,
@@ -98,6 +269,15 @@ impl PodsList {
             filter: String::new(),
             is_filter_mod: false,
             port_forward_popup: None,
+            container_popup: None,
+            delete_dialog: None,
+            marked: BTreeSet::new(),
+            terminating: BTreeSet::new(),
+            workload: None,
+            watch: None,
+            last_update: None,
+            watch_status: None,
+            is_polling: false,
         }
     }
 
@@ -111,31 +291,39 @@ impl PodsList {
             .map(Cell::from)
             .collect::<Row>();
 
-        self.filtered_list = self
-            .original_list
-            .iter()
-            .filter(|item| {
-                if self.filter.is_empty() {
-                    return true;
-                }
-
-                item.name.contains(&self.filter)
-            })
-            .map(|item| item.to_owned())
-            .collect();
+        self.update_filtered_list();
 
         let rows: Vec<Row> = self
             .filtered_list
             .iter()
             .map(|item| {
-                Row::new([
-                    item.name.as_str().into(),
-                    get_status(&item.container_statuses),
-                ])
+                let is_marked = self.marked.contains(&item.name);
+
+                let name = match is_marked {
+                    true => format!("* {}", item.name),
+                    false => item.name.clone(),
+                };
+
+                let status = if item.is_terminating || self.terminating.contains(&item.name) {
+                    Cell::from("Terminating").style(Style::default().fg(Color::Yellow))
+                } else {
+                    get_status(&item.container_statuses)
+                };
+
+                let row = Row::new([Cell::from(name), status]);
+
+                match is_marked {
+                    true => row.style(Style::default().add_modifier(Modifier::BOLD)),
+                    false => row,
+                }
             })
             .collect();
 
-        let block = build_block("Select pod", is_focused && !self.is_filter_mod);
+        let title = match self.marked.len() {
+            0 => self.watch_title(),
+            marked => format!("{} [{marked} marked]", self.watch_title()),
+        };
+        let block = build_block(title.as_str(), is_focused && !self.is_filter_mod);
 
         let table = Table::new(
             rows,
@@ -159,41 +347,253 @@ impl PodsList {
 
             frame.render_widget(filter_widget, layouts[0]);
             frame.render_stateful_widget(table, layouts[1], &mut self.state);
-            return;
+        } else {
+            frame.render_stateful_widget(table, area, &mut self.state);
         }
 
-        frame.render_stateful_widget(table, area, &mut self.state);
-
+        // Drawn whatever the filter, keys go to them first.
         if let Some(port_forward_popup) = &mut self.port_forward_popup {
             port_forward_popup.draw(frame);
         }
+
+        if let Some(container_popup) = &mut self.container_popup {
+            container_popup.draw(frame);
+        }
+
+        if let Some(delete_dialog) = &self.delete_dialog {
+            delete_dialog.draw(frame);
+        }
     }
 
-    pub fn handle_key_event(&mut self, key: KeyEvent) {
-        if let Some(port_forward_popup) = &mut self.port_forward_popup
-            && let Some(port_forward_popup_action) = port_forward_popup.handle_key_event(key)
-        {
-            return match port_forward_popup_action {
-                PortForwardPopupAction::PortForward {
-                    local_port,
-                    app_port,
-                } => {
-                    let pod = self.filtered_list[self.state.selected().unwrap_or(0)].clone();
-
-                    self.event_sender.send(AppEvent::PortForward {
-                        pod_name: pod.name,
-                        local_port,
-                        app_port,
+    fn selected_pod(&self) -> Option<&Pod> {
+        self.filtered_list.get(self.state.selected().unwrap_or(0))
+    }
+
+    fn run_container_action(&self, action: ContainerAction, container: String) {
+        let Some(pod) = self.selected_pod() else {
+            return;
+        };
+
+        match action {
+            ContainerAction::Logs => {
+                self.event_sender
+                    .send(AppEvent::OpenLogs(LogsSource::Container {
+                        context: self.context.clone(),
                         namespace: self.namespace.clone(),
-                    });
+                        pod_name: pod.name.clone(),
+                        container,
+                    }))
+            }
+            ContainerAction::Exec => {
+                self.event_sender
+                    .send(AppEvent::RunInteractive(exec_shell_command(
+                        self.context.as_str(),
+                        self.namespace.as_str(),
+                        pod.name.as_str(),
+                        container.as_str(),
+                    )))
+            }
+        }
+    }
+
+    fn open_container_popup(&mut self, action: ContainerAction) {
+        let Some(pod) = self.selected_pod() else {
+            return;
+        };
+
+        match pod.containers.as_slice() {
+            [] => {}
+            [container] => self.run_container_action(action, container.name.clone()),
+            containers => {
+                self.container_popup = Some(ContainerPopup::new(action, containers.to_vec()))
+            }
+        }
+    }
+
+    fn open_pod_details(&self) {
+        let Some(pod) = self.selected_pod() else {
+            return;
+        };
+
+        self.event_sender.send(AppEvent::OpenPodDetails {
+            context: self.context.clone(),
+            namespace: self.namespace.clone(),
+            pod_name: pod.name.clone(),
+        });
+    }
 
-                    self.port_forward_popup = None;
+    fn toggle_mark(&mut self) {
+        let Some(pod) = self.selected_pod() else {
+            return;
+        };
+
+        let name = pod.name.clone();
+
+        if !self.marked.remove(&name) {
+            self.marked.insert(name);
+        }
+
+        self.select_next();
+    }
+
+    /// Asks to delete the marked pods, or the selected one when none is marked.
+    fn open_delete_dialog(&mut self) {
+        let names: Vec<String> = match self.marked.is_empty() {
+            true => self
+                .selected_pod()
+                .map(|pod| pod.name.clone())
+                .into_iter()
+                .collect(),
+            false => self.marked.iter().cloned().collect(),
+        };
+
+        let title = match names.as_slice() {
+            [] => return,
+            [_] => format!("Delete pod in {}?", self.namespace),
+            names => format!("Delete {} pods in {}?", names.len(), self.namespace),
+        };
+
+        self.delete_dialog = Some(
+            ConfirmDialog::new(title, names)
+                .with_option('g', "Grace period (s)", OptionValue::Number(None))
+                .with_option('f', "Force", OptionValue::Flag(false)),
+        );
+    }
+
+    fn delete(&mut self, dialog: ConfirmDialog) {
+        let names = dialog.items().to_vec();
+
+        self.terminating.extend(names.iter().cloned());
+        self.marked.clear();
+
+        self.event_sender.send(AppEvent::DeletePods {
+            context: self.context.clone(),
+            namespace: self.namespace.clone(),
+            names,
+            grace_period: dialog.number('g'),
+            force: dialog.flag('f'),
+        });
+    }
+
+    /// Shows the pods as they were, the delete request did not go through.
+    pub fn handle_delete_failed(&mut self, names: &[String]) {
+        for name in names {
+            self.terminating.remove(name);
+        }
+    }
+
+    fn selected_resource(&self) -> Option<ResourceRef> {
+        self.selected_pod().map(|pod| ResourceRef {
+            context: self.context.clone(),
+            namespace: self.namespace.clone(),
+            kind: "pod".to_string(),
+            name: pod.name.clone(),
+        })
+    }
+
+    fn open_manifest(&self) {
+        if let Some(resource) = self.selected_resource() {
+            self.event_sender.send(AppEvent::OpenManifest(resource));
+        }
+    }
+
+    fn edit(&self) {
+        if let Some(resource) = self.selected_resource() {
+            self.event_sender.send(AppEvent::EditResource(resource));
+        }
+    }
+
+    fn open_port_forward_popup(&mut self, kind: TargetKind) {
+        let Some(pod) = self.selected_pod() else {
+            return;
+        };
+
+        let target_name = match kind {
+            TargetKind::Deployment => match &pod.deployment {
+                Some(deployment) => deployment.clone(),
+                None => {
+                    self.event_sender
+                        .send(AppEvent::ShowNotification(Log::Warning(format!(
+                            "Pod {} is not managed by a deployment",
+                            pod.name
+                        ))));
+                    return;
                 }
+            },
+            TargetKind::Pod | TargetKind::Service => pod.name.clone(),
+        };
 
-                PortForwardPopupAction::Quit => {
-                    self.port_forward_popup = None;
+        let choices = PortChoice::from_containers(&pod.containers);
+        self.port_forward_popup = Some(PortForwardPopup::new(kind, target_name, choices));
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent, forwarded_ports: &ForwardedPorts) {
+        if let Some(delete_dialog) = &mut self.delete_dialog {
+            match delete_dialog.handle_key_event(key) {
+                Some(ConfirmAction::Confirmed) => {
+                    if let Some(dialog) = self.delete_dialog.take() {
+                        self.delete(dialog);
+                    }
                 }
-            };
+                Some(ConfirmAction::Cancelled) => self.delete_dialog = None,
+                None => {}
+            }
+
+            return;
+        }
+
+        if let Some(container_popup) = &mut self.container_popup {
+            let action = container_popup.action;
+
+            if let Some(popup_action) = container_popup.handle_key_event(key) {
+                self.container_popup = None;
+
+                if let ContainerPopupAction::Selected(container) = popup_action {
+                    self.run_container_action(action, container.name);
+                }
+            }
+
+            return;
+        }
+
+        if let Some(port_forward_popup) = &mut self.port_forward_popup {
+            if let Some(port_forward_popup_action) =
+                port_forward_popup.handle_key_event(key, forwarded_ports)
+            {
+                if let PortForwardPopupAction::PortForward { ports, replace } =
+                    port_forward_popup_action
+                {
+                    // Only pods are moved to a sibling when they go away, kubectl resolves
+                    // deployments by itself.
+                    let selector = match port_forward_popup.target_kind {
+                        TargetKind::Pod => self
+                            .filtered_list
+                            .get(self.state.selected().unwrap_or(0))
+                            .map(|pod| pod.workload_selector())
+                            .unwrap_or_default(),
+                        _ => Default::default(),
+                    };
+
+                    let port_forward = PortForward::new(
+                        self.context.clone(),
+                        self.namespace.clone(),
+                        port_forward_popup.target_kind,
+                        port_forward_popup.target_name.clone(),
+                        ports,
+                        selector,
+                    );
+
+                    self.event_sender.send(if replace {
+                        AppEvent::ReplacePortForward(port_forward)
+                    } else {
+                        AppEvent::PortForward(port_forward)
+                    });
+                }
+
+                self.port_forward_popup = None;
+            }
+
+            return;
         }
 
         if self.is_filter_mod {
@@ -224,13 +624,28 @@ impl PodsList {
             KeyCode::Char('j') | KeyCode::Down => self.select_next(),
             KeyCode::Char('k') | KeyCode::Up => self.select_prev(),
             KeyCode::Char('/') => self.is_filter_mod = true,
-            KeyCode::Char('p') => {
-                let pod_containers = self.filtered_list[self.state.selected().unwrap_or(0)]
-                    .containers
-                    .clone();
-
-                self.port_forward_popup = Some(PortForwardPopup::new(pod_containers));
+            KeyCode::Char('p') => self.open_port_forward_popup(TargetKind::Pod),
+            KeyCode::Char('P') => self.open_port_forward_popup(TargetKind::Deployment),
+            KeyCode::Char('S') => self.event_sender.send(AppEvent::OpenServices {
+                context: self.context.clone(),
+                namespace: self.namespace.clone(),
+            }),
+            KeyCode::Esc if !self.marked.is_empty() => self.marked.clear(),
+            KeyCode::Char('D') | KeyCode::Esc if self.workload.is_some() => {
+                self.show_workload(None);
+                self.event_sender.send(AppEvent::CloseDeploymentPods);
             }
+            KeyCode::Char('D') => self.event_sender.send(AppEvent::OpenDeployments {
+                context: self.context.clone(),
+                namespace: self.namespace.clone(),
+            }),
+            KeyCode::Char('d') => self.open_pod_details(),
+            KeyCode::Char('y') => self.open_manifest(),
+            KeyCode::Char('e') => self.edit(),
+            KeyCode::Char('l') => self.open_container_popup(ContainerAction::Logs),
+            KeyCode::Char('s') => self.open_container_popup(ContainerAction::Exec),
+            KeyCode::Char(' ') => self.toggle_mark(),
+            KeyCode::Char('x') => self.open_delete_dialog(),
             KeyCode::Esc => self.event_sender.send(AppEvent::ClosePodsList),
             _ => {}
         };
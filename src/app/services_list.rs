@@ -0,0 +1,221 @@
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    crossterm::event::KeyEvent,
+    layout::{Constraint, Rect},
+    widgets::{Cell, Row, Table, TableState},
+};
+
+use crate::{
+    app::{
+        common::{
+            ForwardedPorts, PortForwardPopup, PortForwardPopupAction, build_block,
+            get_highlight_style, handle_general_keys, loading_title,
+        },
+        events::{AppEvent, EventSender},
+        side_bar::port_forwards::PortForward,
+    },
+    kubectl::{TargetKind, manifest::ResourceRef, services::Service},
+};
+
+/// Services of the namespace opened in the pods list. Forwarding to a service lets kubectl pick
+/// the backing pod, so the forward survives pod restarts.
+pub struct ServicesList {
+    context: String,
+    namespace: String,
+    list: Vec<Service>,
+    state: TableState,
+    is_loading: bool,
+    port_forward_popup: Option<PortForwardPopup>,
+    event_sender: EventSender,
+}
+
+impl ServicesList {
+    pub fn new(context: String, namespace: String, event_sender: EventSender) -> Self {
+        Self {
+            context,
+            namespace,
+            list: vec![],
+            state: TableState::default(),
+            is_loading: true,
+            port_forward_popup: None,
+            event_sender,
+        }
+    }
+
+    pub fn set_loading(&mut self, is_loading: bool) {
+        self.is_loading = is_loading;
+    }
+
+    pub fn update_list(&mut self, list: Vec<Service>) {
+        self.list = list;
+
+        let selected = match self.state.selected() {
+            _ if self.list.is_empty() => None,
+            Some(selected) => Some(selected.min(self.list.len() - 1)),
+            None => Some(0),
+        };
+
+        self.state.select(selected);
+    }
+
+    pub fn draw(&mut self, area: Rect, frame: &mut Frame, is_focused: bool) {
+        let header = ["Name", "Type", "Cluster IP", "Ports"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>();
+
+        let longest_name = self
+            .list
+            .iter()
+            .map(|service| service.name.len())
+            .max()
+            .unwrap_or(10) as u16;
+
+        let rows: Vec<Row> = self
+            .list
+            .iter()
+            .map(|service| {
+                let ports = service
+                    .ports
+                    .iter()
+                    .map(|port| port.label())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                Row::new([
+                    Cell::from(service.name.as_str()),
+                    Cell::from(service.service_type.as_str()),
+                    Cell::from(service.cluster_ip.as_str()),
+                    Cell::from(ports),
+                ])
+            })
+            .collect();
+
+        let title = loading_title(
+            format!("Services in {}", self.namespace).as_str(),
+            self.is_loading,
+        );
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(longest_name + 3),
+                Constraint::Length(14),
+                Constraint::Length(17),
+                Constraint::Min(5),
+            ],
+        )
+        .header(header)
+        .block(build_block(title.as_str(), is_focused))
+        .row_highlight_style(get_highlight_style());
+
+        frame.render_stateful_widget(table, area, &mut self.state);
+
+        if let Some(port_forward_popup) = &mut self.port_forward_popup {
+            port_forward_popup.draw(frame);
+        }
+    }
+
+    fn selected_service(&self) -> Option<&Service> {
+        self.list.get(self.state.selected()?)
+    }
+
+    fn selected_resource(&self) -> Option<ResourceRef> {
+        self.selected_service().map(|service| ResourceRef {
+            context: self.context.clone(),
+            namespace: self.namespace.clone(),
+            kind: "service".to_string(),
+            name: service.name.clone(),
+        })
+    }
+
+    fn open_manifest(&self) {
+        if let Some(resource) = self.selected_resource() {
+            self.event_sender.send(AppEvent::OpenManifest(resource));
+        }
+    }
+
+    fn edit(&self) {
+        if let Some(resource) = self.selected_resource() {
+            self.event_sender.send(AppEvent::EditResource(resource));
+        }
+    }
+
+    fn open_port_forward_popup(&mut self) {
+        let Some(service) = self.selected_service() else {
+            return;
+        };
+
+        self.port_forward_popup = Some(PortForwardPopup::new(
+            TargetKind::Service,
+            service.name.clone(),
+            service.ports.iter().cloned().map(Into::into).collect(),
+        ));
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent, forwarded_ports: &ForwardedPorts) {
+        if let Some(port_forward_popup) = &mut self.port_forward_popup {
+            if let Some(action) = port_forward_popup.handle_key_event(key, forwarded_ports) {
+                if let PortForwardPopupAction::PortForward { ports, replace } = action {
+                    let port_forward = PortForward::new(
+                        self.context.clone(),
+                        self.namespace.clone(),
+                        port_forward_popup.target_kind,
+                        port_forward_popup.target_name.clone(),
+                        ports,
+                        Default::default(),
+                    );
+
+                    self.event_sender.send(if replace {
+                        AppEvent::ReplacePortForward(port_forward)
+                    } else {
+                        AppEvent::PortForward(port_forward)
+                    });
+                }
+
+                self.port_forward_popup = None;
+            }
+
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => self.select_prev(),
+            KeyCode::Char('p') | KeyCode::Enter => self.open_port_forward_popup(),
+            KeyCode::Char('y') => self.open_manifest(),
+            KeyCode::Char('e') => self.edit(),
+            KeyCode::Esc => self.event_sender.send(AppEvent::CloseServices),
+            _ => {}
+        };
+
+        handle_general_keys(key, &self.event_sender);
+    }
+
+    fn select_next(&mut self) {
+        if self.list.is_empty() {
+            return;
+        }
+
+        let i = match self.state.selected() {
+            Some(i) if i + 1 < self.list.len() => i + 1,
+            _ => 0,
+        };
+
+        self.state.select(Some(i));
+    }
+
+    fn select_prev(&mut self) {
+        if self.list.is_empty() {
+            return;
+        }
+
+        let i = match self.state.selected() {
+            Some(i) if i > 0 => i - 1,
+            _ => self.list.len() - 1,
+        };
+
+        self.state.select(Some(i));
+    }
+}
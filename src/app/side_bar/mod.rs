@@ -29,11 +29,9 @@ impl From<SideBar> for SideBarCache {
 }
 
 impl SideBar {
-    pub fn from_cache(&mut self, value: SideBarCache, event_sender: EventSender) -> Self {
+    pub fn from_cache(value: SideBarCache, event_sender: EventSender) -> Self {
         Self {
-            port_forwards: self
-                .port_forwards
-                .build_from_cache(value.port_forwards, event_sender.clone()),
+            port_forwards: PortForwardsList::from_cache(value.port_forwards, event_sender.clone()),
 
             recent_namespaces: RecentNamespacesList::from_cache(
                 value.recent_namespaces,
@@ -52,7 +50,11 @@ impl SideBar {
     pub fn draw(&mut self, area: Rect, frame: &mut Frame, focus: Option<SideBarWindow>) {
         let layouts = Layout::default()
             .direction(Direction::Vertical)
-            .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
+            .constraints(vec![
+                Constraint::Percentage(35),
+                Constraint::Percentage(40),
+                Constraint::Percentage(25),
+            ])
             .split(area);
 
         self.recent_namespaces.draw(
@@ -61,6 +63,13 @@ impl SideBar {
             focus == Some(SideBarWindow::RecentNamespaces),
         );
 
+        self.port_forwards.draw_profiles(
+            layouts[2],
+            frame,
+            focus == Some(SideBarWindow::PortForwardProfiles),
+        );
+
+        // Drawn last, its profile name input pops up over the other panels.
         self.port_forwards.draw(
             layouts[1],
             frame,
@@ -0,0 +1,513 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::OpenOptions,
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    crossterm::event::KeyEvent,
+    widgets::{Clear, Paragraph},
+};
+
+use crate::{
+    app::{
+        common::{ConfirmAction, ConfirmDialog, build_block, centered_rect, draw_text_input},
+        events::{AppEvent, Log},
+        side_bar::port_forwards::{PortForward, PortForwardsList},
+    },
+    error::{AppError, AppResult},
+    files::DIR_PATH,
+};
+
+/// Used for forwards without a template of their own.
+pub const DEFAULT_ENV_TEMPLATE: &str = "{{pod}}_PORT";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// `NAME=value` lines, as read by dotenv loaders and docker compose.
+    Env,
+    /// `export NAME='value'` lines, to be sourced or `eval`ed.
+    Shell,
+    /// A single object of names to values.
+    Json,
+}
+
+impl ExportFormat {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format {
+            "env" | "dotenv" => Some(ExportFormat::Env),
+            "shell" | "sh" => Some(ExportFormat::Shell),
+            "json" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+
+    fn file_name(&self) -> &'static str {
+        match self {
+            ExportFormat::Env => "port-forwards.env",
+            ExportFormat::Shell => "port-forwards.sh",
+            ExportFormat::Json => "port-forwards.json",
+        }
+    }
+}
+
+/// Where to export to, asked for once the format is picked.
+#[derive(Debug, Clone)]
+pub(super) struct ExportPathInput {
+    format: ExportFormat,
+    path: String,
+    /// Asks before replacing a file that already exists at `path`.
+    overwrite_dialog: Option<ConfirmDialog>,
+}
+
+impl PortForward {
+    /// One variable per forwarded port. The template names the variable, e.g. `{{pod}}_PORT`,
+    /// or the whole assignment, e.g. `DATABASE_URL=postgres://localhost:{{local_port}}/app`.
+    /// Without a value part the value is the local port.
+    ///
+    /// Placeholders: `{{pod}}` (or `{{name}}`), `{{kind}}`, `{{namespace}}`, `{{context}}`,
+    /// `{{local_port}}` and `{{app_port}}`.
+    pub fn env_vars(&self) -> Vec<(String, String)> {
+        let template = self.env_template.as_deref().unwrap_or(DEFAULT_ENV_TEMPLATE);
+
+        let (name_template, value_template) = template
+            .split_once('=')
+            .unwrap_or((template, "{{local_port}}"));
+
+        let ports = self.port_mappings();
+
+        // Without a port in the name, the variables of a multi port forward would collide.
+        let needs_suffix = ports.len() > 1
+            && !name_template.contains("{{local_port}}")
+            && !name_template.contains("{{app_port}}");
+
+        ports
+            .iter()
+            .map(|ports| {
+                let render = |template: &str| {
+                    template
+                        .replace("{{pod}}", self.target_name.as_str())
+                        .replace("{{name}}", self.target_name.as_str())
+                        .replace("{{kind}}", self.kind.prefix())
+                        .replace("{{namespace}}", self.namespace.as_str())
+                        .replace("{{context}}", self.context.as_str())
+                        .replace("{{local_port}}", ports.local_port.to_string().as_str())
+                        .replace("{{app_port}}", ports.app_port.to_string().as_str())
+                };
+
+                let mut name = env_var_name(render(name_template.trim()).as_str());
+
+                if needs_suffix {
+                    name.push_str(format!("_{}", ports.app_port).as_str());
+                }
+
+                (name, render(value_template.trim()))
+            })
+            .collect()
+    }
+}
+
+/// Upper case with anything that is not valid in a variable name replaced by `_`,
+/// so `{{pod}}_PORT` of `api-server` becomes `API_SERVER_PORT`.
+fn env_var_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() {
+                ch.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    match name.chars().next() {
+        Some(first) if first.is_ascii_digit() => format!("_{name}"),
+        _ => name,
+    }
+}
+
+pub fn export_port_forwards<'a>(
+    port_forwards: impl IntoIterator<Item = &'a PortForward>,
+    format: ExportFormat,
+) -> AppResult<String> {
+    let vars: Vec<(String, String)> = port_forwards
+        .into_iter()
+        .flat_map(PortForward::env_vars)
+        .collect();
+
+    // The last one would win silently, e.g. for the same pod name in two namespaces.
+    let mut names = BTreeSet::new();
+
+    if let Some((name, _)) = vars.iter().find(|(name, _)| !names.insert(name.as_str())) {
+        return Err(AppError::GeneralError(anyhow::anyhow!(
+            "Several port forwards export {name}, give them distinct variable templates"
+        )));
+    }
+
+    let output = match format {
+        ExportFormat::Env => vars
+            .iter()
+            .map(|(name, value)| format!("{name}={}\n", env_value(value)))
+            .collect(),
+        ExportFormat::Shell => vars
+            .iter()
+            .map(|(name, value)| format!("export {name}={}\n", shell_value(value)))
+            .collect(),
+        ExportFormat::Json => {
+            let vars: BTreeMap<String, String> = vars.into_iter().collect();
+
+            let mut json = serde_json::to_string_pretty(&vars)
+                .context("failed to serialize port forwards")
+                .map_err(AppError::GeneralError)?;
+
+            json.push('\n');
+            json
+        }
+    };
+
+    Ok(output)
+}
+
+fn env_value(value: &str) -> String {
+    let needs_quotes = value
+        .chars()
+        .any(|ch| ch.is_whitespace() || matches!(ch, '#' | '"' | '\'' | '$' | '\\'));
+
+    if needs_quotes {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn shell_value(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+impl PortForwardsList {
+    pub(super) fn draw_export_inputs(&self, frame: &mut Frame) {
+        if let Some(template) = &self.env_template_input {
+            draw_text_input(
+                frame,
+                "Variable template, e.g. {{pod}}_PORT or URL=http://localhost:{{local_port}}",
+                template,
+            );
+        }
+
+        if self.is_export_prompt_open {
+            let area = centered_rect(frame.area(), 44, 3);
+
+            frame.render_widget(Clear, area);
+            frame.render_widget(
+                Paragraph::new("[e] .env   [s] shell   [j] JSON")
+                    .block(build_block("Export active port forwards", true)),
+                area,
+            );
+        }
+
+        if let Some(input) = &self.export_path_input {
+            match &input.overwrite_dialog {
+                Some(dialog) => dialog.draw(frame),
+                None => draw_text_input(frame, "Export to", input.path.as_str()),
+            }
+        }
+    }
+
+    pub(super) fn open_env_template_input(&mut self, index: usize) {
+        let template = self.list[index]
+            .env_template
+            .clone()
+            .unwrap_or(DEFAULT_ENV_TEMPLATE.to_string());
+
+        self.env_template_input = Some(template);
+    }
+
+    /// Returns `true` when the key was consumed by the template input or the export prompts.
+    pub(super) fn handle_export_inputs(&mut self, key: KeyEvent) -> bool {
+        if self.is_export_prompt_open {
+            let format = match key.code {
+                KeyCode::Char('e') => Some(ExportFormat::Env),
+                KeyCode::Char('s') => Some(ExportFormat::Shell),
+                KeyCode::Char('j') => Some(ExportFormat::Json),
+                _ => None,
+            };
+
+            if let Some(format) = format {
+                self.open_export_path_input(format);
+            }
+
+            if format.is_some() || key.code == KeyCode::Esc {
+                self.is_export_prompt_open = false;
+            }
+
+            return true;
+        }
+
+        if self.handle_export_path_input(key) {
+            return true;
+        }
+
+        let Some(template) = &mut self.env_template_input else {
+            return false;
+        };
+
+        match key.code {
+            KeyCode::Char(ch) => template.push(ch),
+            KeyCode::Backspace => {
+                template.pop();
+            }
+            KeyCode::Enter => {
+                let template = template.trim().to_string();
+                self.env_template_input = None;
+
+                if let Some(index) = self.selected_index() {
+                    let item = &mut self.list[index];
+
+                    item.env_template = match template.as_str() {
+                        "" | DEFAULT_ENV_TEMPLATE => None,
+                        _ => Some(template),
+                    };
+                }
+            }
+            KeyCode::Esc => self.env_template_input = None,
+            _ => {}
+        }
+
+        true
+    }
+
+    fn open_export_path_input(&mut self, format: ExportFormat) {
+        if !self.list.iter().any(|item| item.is_active()) {
+            self.event_sender
+                .send(AppEvent::ShowNotification(Log::Warning(
+                    "There are no active port forwards to export".to_string(),
+                )));
+            return;
+        }
+
+        // The app dir by default, the working directory is wherever the app was started from.
+        let path = Path::new(DIR_PATH).join(format.file_name());
+
+        self.export_path_input = Some(ExportPathInput {
+            format,
+            path: path.to_string_lossy().into_owned(),
+            overwrite_dialog: None,
+        });
+    }
+
+    fn handle_export_path_input(&mut self, key: KeyEvent) -> bool {
+        let Some(input) = &mut self.export_path_input else {
+            return false;
+        };
+
+        if let Some(dialog) = &mut input.overwrite_dialog {
+            match dialog.handle_key_event(key) {
+                Some(ConfirmAction::Confirmed) => {
+                    let (format, path) = (input.format, PathBuf::from(input.path.as_str()));
+                    self.export_path_input = None;
+                    self.export(format, &path, true);
+                }
+                // Back to the path, to pick another one.
+                Some(ConfirmAction::Cancelled) => input.overwrite_dialog = None,
+                None => {}
+            }
+
+            return true;
+        }
+
+        match key.code {
+            KeyCode::Char(ch) => input.path.push(ch),
+            KeyCode::Backspace => {
+                input.path.pop();
+            }
+            KeyCode::Enter if !input.path.trim().is_empty() => {
+                let (format, path) = (input.format, PathBuf::from(input.path.trim()));
+
+                if self.export(format, &path, false) {
+                    self.export_path_input = None;
+                } else if let Some(input) = &mut self.export_path_input {
+                    input.overwrite_dialog = Some(ConfirmDialog::new(
+                        "Overwrite existing file".to_string(),
+                        vec![path.display().to_string()],
+                    ));
+                }
+            }
+            KeyCode::Esc => self.export_path_input = None,
+            _ => {}
+        }
+
+        true
+    }
+
+    /// Writes the active forwards to `path`. Returns `false`, without writing anything, when the
+    /// file already exists and `overwrite` is not set.
+    fn export(&mut self, format: ExportFormat, path: &Path, overwrite: bool) -> bool {
+        let active: Vec<&PortForward> = self.list.iter().filter(|item| item.is_active()).collect();
+
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .create_new(!overwrite)
+            .open(path);
+
+        let result = match file {
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => return false,
+            Err(err) => Err(AppError::GeneralError(
+                anyhow::Error::new(err).context(format!("failed to write {}", path.display())),
+            )),
+            Ok(mut file) => {
+                export_port_forwards(active.iter().copied(), format).and_then(|output| {
+                    file.write_all(output.as_bytes())
+                        .with_context(|| format!("failed to write {}", path.display()))
+                        .map_err(AppError::GeneralError)
+                })
+            }
+        };
+
+        let log = match result {
+            Ok(()) => {
+                let path = std::path::absolute(path).unwrap_or(path.to_path_buf());
+                Log::Info(format!(
+                    "Exported {} port forwards to {}",
+                    active.len(),
+                    path.display()
+                ))
+            }
+            Err(err) => Log::Error(err.to_string()),
+        };
+
+        self.event_sender.send(AppEvent::ShowNotification(log));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kubectl::{PortMapping, TargetKind};
+
+    fn port_forward(ports: Vec<PortMapping>, env_template: Option<&str>) -> PortForward {
+        PortForward {
+            env_template: env_template.map(String::from),
+            ..PortForward::new(
+                "prod".to_string(),
+                "shop".to_string(),
+                TargetKind::Service,
+                "api-server".to_string(),
+                ports,
+                BTreeMap::new(),
+            )
+        }
+    }
+
+    #[test]
+    fn names_variables_after_the_default_template() {
+        let vars = port_forward(vec![PortMapping::new(8080, 80)], None).env_vars();
+
+        assert_eq!(
+            vars,
+            vec![("API_SERVER_PORT".to_string(), "8080".to_string())]
+        );
+    }
+
+    #[test]
+    fn renders_assignment_templates() {
+        let template = "{{namespace}}_url=http://localhost:{{local_port}}/{{kind}}";
+        let vars = port_forward(vec![PortMapping::new(8080, 80)], Some(template)).env_vars();
+
+        assert_eq!(
+            vars,
+            vec![(
+                "SHOP_URL".to_string(),
+                "http://localhost:8080/svc".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn suffixes_variables_of_multi_port_forwards() {
+        let ports = vec![PortMapping::new(8080, 80), PortMapping::new(8443, 443)];
+        let names: Vec<String> = port_forward(ports, None)
+            .env_vars()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+
+        assert_eq!(names, vec!["API_SERVER_PORT_80", "API_SERVER_PORT_443"]);
+    }
+
+    #[test]
+    fn keeps_multi_port_names_with_a_port_placeholder() {
+        let ports = vec![PortMapping::new(8080, 80), PortMapping::new(8443, 443)];
+        let names: Vec<String> = port_forward(ports, Some("PORT_{{app_port}}"))
+            .env_vars()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+
+        assert_eq!(names, vec!["PORT_80", "PORT_443"]);
+    }
+
+    #[test]
+    fn makes_variable_names_valid() {
+        assert_eq!(env_var_name("api-server.v2_port"), "API_SERVER_V2_PORT");
+        assert_eq!(env_var_name("8080_port"), "_8080_PORT");
+        assert_eq!(env_var_name("päth"), "P_TH");
+    }
+
+    #[test]
+    fn quotes_env_values_only_when_needed() {
+        assert_eq!(env_value("8080"), "8080");
+        assert_eq!(env_value("http://localhost:8080"), "http://localhost:8080");
+        assert_eq!(env_value("a b"), "\"a b\"");
+        assert_eq!(env_value("#1"), "\"#1\"");
+        assert_eq!(env_value("$HOME"), "\"$HOME\"");
+        assert_eq!(env_value(r#"say "hi""#), r#""say \"hi\"""#);
+        assert_eq!(env_value(r"C:\dir"), r#""C:\\dir""#);
+    }
+
+    #[test]
+    fn single_quotes_shell_values() {
+        assert_eq!(shell_value("8080"), "'8080'");
+        assert_eq!(shell_value("$HOME `id`"), "'$HOME `id`'");
+        assert_eq!(shell_value("it's"), r"'it'\''s'");
+    }
+
+    #[test]
+    fn rejects_duplicate_variable_names() {
+        let forwards = [
+            port_forward(vec![PortMapping::new(8080, 80)], None),
+            port_forward(vec![PortMapping::new(9090, 80)], None),
+        ];
+
+        let err = export_port_forwards(&forwards, ExportFormat::Json).unwrap_err();
+        assert!(err.to_string().contains("API_SERVER_PORT"));
+    }
+
+    #[test]
+    fn exports_every_format() {
+        let forwards = [port_forward(
+            vec![PortMapping::new(8080, 80)],
+            Some("URL=http://localhost:{{local_port}}/a b"),
+        )];
+
+        assert_eq!(
+            export_port_forwards(&forwards, ExportFormat::Env).unwrap(),
+            "URL=\"http://localhost:8080/a b\"\n"
+        );
+        assert_eq!(
+            export_port_forwards(&forwards, ExportFormat::Shell).unwrap(),
+            "export URL='http://localhost:8080/a b'\n"
+        );
+        assert_eq!(
+            export_port_forwards(&forwards, ExportFormat::Json).unwrap(),
+            "{\n  \"URL\": \"http://localhost:8080/a b\"\n}\n"
+        );
+    }
+}
@@ -0,0 +1,274 @@
+use std::{
+    net::Ipv4Addr,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::KeyCode;
+use ratatui::{Frame, crossterm::event::KeyEvent};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::TcpStream,
+    time::timeout,
+};
+
+use crate::app::{
+    common::draw_text_input,
+    events::{AppEvent, Log},
+    side_bar::port_forwards::{BackendState, ForwardState, PortForward, PortForwardsList},
+};
+
+const PROBE_INTERVAL: Duration = Duration::from_secs(10);
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+/// How long a bare TCP probe keeps the connection open. kubectl accepts on its own listener and
+/// only then dials the pod, closing or resetting the connection right away when that fails.
+const TCP_SETTLE_TIME: Duration = Duration::from_millis(300);
+
+/// Outcome of the last probe of a forward.
+#[derive(Debug, Clone)]
+pub enum Health {
+    Healthy(Duration),
+    Unhealthy(String),
+}
+
+/// One round trip against a forward's local end: a TCP connect that has to stay open for
+/// `TCP_SETTLE_TIME`, or a `GET` of `http_path` when the forward has one configured.
+#[derive(Debug, Clone)]
+pub struct Probe {
+    /// Context, namespace and target of the probed forward, so the result is not taken for
+    /// another forward that got its local port meanwhile.
+    pub context: String,
+    namespace: String,
+    target: String,
+    pub local_port: u16,
+    /// Port actually connected to. Differs from `local_port` for proxied forwards, which are
+    /// probed on kubectl's side so the probe neither starts kubectl nor shows up in the stats.
+    port: u16,
+    http_path: Option<String>,
+}
+
+impl Probe {
+    pub async fn run(&self) -> Health {
+        let started_at = Instant::now();
+
+        match timeout(PROBE_TIMEOUT, self.round_trip()).await {
+            Ok(Ok(())) => Health::Healthy(started_at.elapsed()),
+            Ok(Err(err)) => Health::Unhealthy(err),
+            Err(_) => Health::Unhealthy(format!("no answer within {}s", PROBE_TIMEOUT.as_secs())),
+        }
+    }
+
+    async fn round_trip(&self) -> Result<(), String> {
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, self.port))
+            .await
+            .map_err(|err| format!("connect failed: {err}"))?;
+
+        let Some(path) = &self.http_path else {
+            return settle(&mut stream).await;
+        };
+
+        let request = format!(
+            "GET {path} HTTP/1.1\r\nHost: localhost:{}\r\nConnection: close\r\n\r\n",
+            self.local_port
+        );
+
+        stream
+            .write_all(request.as_bytes())
+            .await
+            .map_err(|err| format!("request failed: {err}"))?;
+
+        let mut status_line = String::new();
+
+        BufReader::new(stream)
+            .read_line(&mut status_line)
+            .await
+            .map_err(|err| format!("response failed: {err}"))?;
+
+        // `HTTP/1.1 200 OK`
+        let status = status_line
+            .split_whitespace()
+            .nth(1)
+            .and_then(|status| status.parse::<u16>().ok())
+            .ok_or_else(|| "not an HTTP response".to_string())?;
+
+        if (200..400).contains(&status) {
+            Ok(())
+        } else {
+            Err(format!("GET {path} returned {status}"))
+        }
+    }
+
+    fn is_for(&self, port_forward: &PortForward) -> bool {
+        port_forward.context == self.context
+            && port_forward.namespace == self.namespace
+            && port_forward.target() == self.target
+            && port_forward.local_port == self.local_port
+    }
+}
+
+/// Fails when the connection is closed or reset before `TCP_SETTLE_TIME` is over. Protocols
+/// where the server speaks first may send data instead, which proves the pod answered.
+async fn settle(stream: &mut TcpStream) -> Result<(), String> {
+    let mut buf = [0; 64];
+
+    match timeout(TCP_SETTLE_TIME, stream.read(&mut buf)).await {
+        Err(_) => Ok(()),
+        Ok(Ok(0)) => Err("connection closed by kubectl".to_string()),
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(err)) => Err(format!("connection dropped: {err}")),
+    }
+}
+
+impl PortForward {
+    fn probe(&self) -> Option<Probe> {
+        if self.state != ForwardState::Up {
+            return None;
+        }
+
+        let port = match &self.proxy {
+            // An idle proxy has nothing behind it to probe yet.
+            Some(proxy) => match proxy.backend_state() {
+                BackendState::Up => proxy.backend_port(self.app_port)?,
+                BackendState::Idle | BackendState::Starting => return None,
+            },
+            None => {
+                self.pid?;
+                self.local_port
+            }
+        };
+
+        Some(Probe {
+            context: self.context.clone(),
+            namespace: self.namespace.clone(),
+            target: self.target(),
+            local_port: self.local_port,
+            port,
+            http_path: self.health_path.clone(),
+        })
+    }
+}
+
+impl PortForwardsList {
+    /// Probes to run now, one per active forward, every `PROBE_INTERVAL`. Forwards that cannot be
+    /// probed lose their last result, it would only be stale.
+    pub fn due_probes(&mut self) -> Vec<Probe> {
+        if self
+            .last_probe
+            .is_some_and(|last_probe| last_probe.elapsed() < PROBE_INTERVAL)
+        {
+            return vec![];
+        }
+
+        self.last_probe = Some(Instant::now());
+
+        self.list
+            .iter_mut()
+            .filter_map(|item| {
+                let probe = item.probe();
+
+                if probe.is_none() {
+                    item.health = None;
+                }
+
+                probe
+            })
+            .collect()
+    }
+
+    pub fn handle_probe_result(&mut self, probe: Probe, health: Health) {
+        let Some(item) = self
+            .list
+            .iter_mut()
+            .find(|item| probe.is_for(item) && item.state == ForwardState::Up)
+        else {
+            return;
+        };
+
+        let was_healthy = !matches!(item.health, Some(Health::Unhealthy(_)));
+
+        if let Health::Unhealthy(reason) = &health
+            && was_healthy
+        {
+            self.event_sender
+                .send(AppEvent::ShowNotification(Log::Warning(format!(
+                    "Port forward {} {} is unhealthy: {reason}",
+                    item.target(),
+                    item.ports_label()
+                ))));
+        }
+
+        item.health = Some(health);
+    }
+
+    pub(super) fn draw_health_path_input(&self, frame: &mut Frame) {
+        if let Some(path) = &self.health_path_input {
+            draw_text_input(frame, "HTTP health path, empty for TCP only", path);
+        }
+    }
+
+    pub(super) fn open_health_path_input(&mut self, index: usize) {
+        let path = self.list[index].health_path.clone().unwrap_or_default();
+        self.health_path_input = Some(path);
+    }
+
+    /// Returns `true` when the key was consumed by the health path input.
+    pub(super) fn handle_health_path_input(&mut self, key: KeyEvent) -> bool {
+        let Some(path) = &mut self.health_path_input else {
+            return false;
+        };
+
+        match key.code {
+            KeyCode::Char(ch) => path.push(ch),
+            KeyCode::Backspace => {
+                path.pop();
+            }
+            KeyCode::Enter => {
+                let path = path.trim().to_string();
+                self.health_path_input = None;
+                self.set_health_path(path);
+            }
+            KeyCode::Esc => self.health_path_input = None,
+            _ => {}
+        }
+
+        true
+    }
+
+    fn set_health_path(&mut self, path: String) {
+        let Some(index) = self.selected_index() else {
+            return;
+        };
+
+        let item = &mut self.list[index];
+
+        let message = if path.is_empty() {
+            item.health_path = None;
+            format!(
+                "Probing {} {} with TCP connects",
+                item.target(),
+                item.ports_label()
+            )
+        } else {
+            let path = if path.starts_with('/') {
+                path
+            } else {
+                format!("/{path}")
+            };
+
+            let message = format!(
+                "Probing {} {} with GET {path}",
+                item.target(),
+                item.ports_label()
+            );
+
+            item.health_path = Some(path);
+            message
+        };
+
+        // The result of the old probe does not apply anymore.
+        item.health = None;
+        self.last_probe = None;
+
+        self.event_sender
+            .send(AppEvent::ShowNotification(Log::Info(message)));
+    }
+}
@@ -0,0 +1,1016 @@
+mod export;
+mod health;
+mod profiles;
+mod proxy;
+mod session;
+
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    crossterm::event::KeyEvent,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::{
+        cache::{PortForwardsListCache, StateCache},
+        common::{
+            ForwardedPorts, build_block, get_highlight_style, handle_general_keys, loading_title,
+        },
+        events::{AppEvent, EventSender, Log},
+        jobs::Job,
+        logs_view::LogsSource,
+    },
+    error::{AppError, AppResult},
+    kubectl::{
+        self, KubectlStream, PortForwardProcess, PortMapping, TargetKind, pods::resolve_pod,
+    },
+};
+
+use export::ExportPathInput;
+pub use export::{ExportFormat, export_port_forwards};
+pub use health::{Health, Probe};
+pub use profiles::PortForwardProfile;
+pub use proxy::{BackendState, Proxy};
+
+const MAX_RESTART_ATTEMPTS: u32 = 8;
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+/// How long a forward must stay up before an exit no longer counts against its restart attempts.
+const MIN_STABLE_UPTIME: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct PortForwardsList {
+    list: Vec<PortForward>,
+    state: ListState,
+    profiles: Vec<PortForwardProfile>,
+    profiles_state: ListState,
+    profile_name_input: Option<String>,
+    health_path_input: Option<String>,
+    last_probe: Option<Instant>,
+    env_template_input: Option<String>,
+    is_export_prompt_open: bool,
+    export_path_input: Option<ExportPathInput>,
+    restore_on_start: bool,
+    event_sender: EventSender,
+    is_loading: bool,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct PortForward {
+    /// Missing from the state saved before kubeconfig contexts, see `LegacyAppCache`.
+    #[serde(default)]
+    pub context: String,
+    pub namespace: String,
+    #[serde(default)]
+    pub kind: TargetKind,
+    /// Name of the pod, service or deployment, depending on `kind`.
+    #[serde(alias = "pod_name")]
+    pub target_name: String,
+    pub local_port: u16,
+    pub app_port: u16,
+    /// Ports served by the same kubectl process in addition to `local_port` -> `app_port`.
+    #[serde(default)]
+    pub extra_ports: Vec<PortMapping>,
+    pub pid: Option<u32>,
+    /// Labels of the pod's workload, used to find a replacement pod after restarts.
+    /// Services and deployments are resolved by kubectl itself and leave it empty.
+    #[serde(default)]
+    pub selector: BTreeMap<String, String>,
+    #[serde(default)]
+    pub restarts: u32,
+    /// Served by the app's own proxy, which starts kubectl on the first connection.
+    #[serde(default)]
+    pub proxied: bool,
+    /// Probed with an HTTP `GET` of this path instead of a bare TCP connect.
+    #[serde(default)]
+    pub health_path: Option<String>,
+    /// Names the forward's variables when exporting, see `PortForward::env_vars`.
+    #[serde(default)]
+    pub env_template: Option<String>,
+    /// Whether the forward was active when the state was saved, to restore it on the next start.
+    #[serde(default)]
+    pub was_active: bool,
+    #[serde(skip)]
+    pub state: ForwardState,
+    #[serde(skip)]
+    restart_attempt: u32,
+    #[serde(skip)]
+    up_since: Option<Instant>,
+    #[serde(skip)]
+    monitor: Option<KubectlStream>,
+    #[serde(skip)]
+    proxy: Option<Proxy>,
+    #[serde(skip)]
+    health: Option<Health>,
+    /// Picked for saving as a profile.
+    #[serde(skip)]
+    is_marked: bool,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForwardState {
+    #[default]
+    Inactive,
+    Starting,
+    Up,
+    Reconnecting,
+    Failed,
+}
+
+impl PortForward {
+    pub fn new(
+        context: String,
+        namespace: String,
+        kind: TargetKind,
+        target_name: String,
+        ports: Vec<PortMapping>,
+        selector: BTreeMap<String, String>,
+    ) -> Self {
+        // The first mapping identifies the forward, the rest share its process.
+        let (primary, extra_ports) = match ports.split_first() {
+            Some((primary, extra_ports)) => (*primary, extra_ports.to_vec()),
+            None => (PortMapping::default(), vec![]),
+        };
+
+        Self {
+            context,
+            namespace,
+            kind,
+            target_name,
+            local_port: primary.local_port,
+            app_port: primary.app_port,
+            extra_ports,
+            selector,
+            ..Default::default()
+        }
+    }
+
+    /// The forward as configured, without anything of a running one, to save it or start it
+    /// afresh.
+    pub fn config_copy(&self) -> Self {
+        Self {
+            proxied: self.proxied,
+            health_path: self.health_path.clone(),
+            env_template: self.env_template.clone(),
+            ..Self::new(
+                self.context.clone(),
+                self.namespace.clone(),
+                self.kind,
+                self.target_name.clone(),
+                self.port_mappings(),
+                self.selector.clone(),
+            )
+        }
+    }
+
+    fn from_process(context: &str, namespace: &str, process: PortForwardProcess) -> Self {
+        let mut port_forward = PortForward::new(
+            context.to_string(),
+            process.namespace.unwrap_or(namespace.to_string()),
+            process.kind,
+            process.target_name,
+            process.ports,
+            BTreeMap::new(),
+        );
+
+        port_forward.pid = Some(process.pid);
+        port_forward.state = ForwardState::Up;
+        port_forward
+    }
+
+    /// Whether the recorded PID still runs this very forward. PIDs get reused, e.g. after a
+    /// reboot, so a live process alone proves nothing.
+    pub fn is_running(&self) -> bool {
+        self.pid
+            .and_then(kubectl::inspect_port_forward)
+            .is_some_and(|process| self.matches_process(&process))
+    }
+
+    fn matches_process(&self, process: &PortForwardProcess) -> bool {
+        process
+            .context
+            .as_deref()
+            .is_none_or(|context| context == self.context)
+            && process
+                .namespace
+                .as_deref()
+                .is_none_or(|namespace| namespace == self.namespace)
+            && process.kind == self.kind
+            && process.target_name == self.target_name
+            && process.ports == self.port_mappings()
+    }
+
+    pub fn port_mappings(&self) -> Vec<PortMapping> {
+        let mut ports = vec![PortMapping::new(self.local_port, self.app_port)];
+        ports.extend(self.extra_ports.iter().copied());
+        ports
+    }
+
+    /// `8080 -> 80`, or every pair for forwards serving several ports.
+    pub fn ports_label(&self) -> String {
+        self.port_mappings()
+            .iter()
+            .map(|ports| format!("{} -> {}", ports.local_port, ports.app_port))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// The pod may change between restarts, so it is not part of a forward's identity.
+    fn is_same_forward(&self, other: &PortForward) -> bool {
+        self.context == other.context
+            && self.namespace == other.namespace
+            && self.local_port == other.local_port
+            && self.app_port == other.app_port
+    }
+
+    /// `pod/name`, `svc/name` or `deploy/name`, as shown to the user and passed to kubectl.
+    pub fn target(&self) -> String {
+        self.kind.target(self.target_name.as_str())
+    }
+
+    pub fn log_path(&self) -> PathBuf {
+        kubectl::port_forward_log_path(
+            self.context.as_str(),
+            self.namespace.as_str(),
+            &self.port_mappings(),
+        )
+    }
+
+    fn is_active(&self) -> bool {
+        self.pid.is_some()
+            || self.proxy.is_some()
+            || matches!(
+                self.state,
+                ForwardState::Starting | ForwardState::Reconnecting
+            )
+    }
+
+    fn restart_delay(&self) -> Duration {
+        let delay = Duration::from_secs(1 << self.restart_attempt.min(6));
+        delay.min(MAX_RESTART_DELAY)
+    }
+
+    fn watch_process(&mut self, event_sender: &EventSender) {
+        let Some(pid) = self.pid else {
+            self.monitor = None;
+            return;
+        };
+
+        let event_sender = event_sender.clone();
+        self.up_since.get_or_insert_with(Instant::now);
+
+        self.monitor = Some(kubectl::monitor_port_forward(
+            pid,
+            self.log_path(),
+            move || event_sender.send(AppEvent::PortForwardExited { pid }),
+        ));
+    }
+}
+
+/// The forward's pod, or a running pod of the same workload if it is gone. Services and
+/// deployments are handed to kubectl as is, it picks the pod on every start.
+async fn resolve_target_name(port_forward: &PortForward) -> AppResult<String> {
+    match port_forward.kind {
+        TargetKind::Pod => {
+            resolve_pod(
+                port_forward.context.as_str(),
+                port_forward.namespace.as_str(),
+                port_forward.target_name.as_str(),
+                &port_forward.selector,
+            )
+            .await
+        }
+        TargetKind::Service | TargetKind::Deployment => Ok(port_forward.target_name.clone()),
+    }
+}
+
+/// The `kubectl port-forward` processes of `context` that were started outside the app. Those
+/// started without `-n` run in the namespace of the context.
+pub async fn find_outside_port_forwards(context: String) -> AppResult<Vec<PortForward>> {
+    let namespace = kubectl::context::get_context_namespace(context.as_str()).await?;
+    // The app switches contexts without touching the kubeconfig, so the shown one may not be
+    // the current one. Without a current context, only forwards naming theirs are taken.
+    let current_context = kubectl::context::get_current_context().await.ok();
+
+    // Reads every process's command line.
+    let processes = tokio::task::spawn_blocking(kubectl::find_port_forward_processes)
+        .await
+        .context("Failed to look for port forward processes")
+        .map_err(AppError::PortForwardError)?;
+
+    Ok(processes
+        .into_iter()
+        // kubectl without `--context` uses the kubeconfig's current context.
+        .filter(|process| {
+            process
+                .context
+                .as_ref()
+                .or(current_context.as_ref())
+                .is_some_and(|process_context| *process_context == context)
+        })
+        .map(|process| PortForward::from_process(context.as_str(), namespace.as_str(), process))
+        .collect())
+}
+
+/// Starts the forward, moving it to a running pod of the same workload if its pod is gone.
+pub async fn launch_port_forward(port_forward: PortForward) -> AppResult<PortForward> {
+    let target_name = resolve_target_name(&port_forward).await?;
+
+    let mut port_forward = PortForward {
+        target_name,
+        ..port_forward
+    };
+
+    let pid = kubectl::start_port_forward(
+        port_forward.context.as_str(),
+        port_forward.namespace.as_str(),
+        port_forward.target().as_str(),
+        &port_forward.port_mappings(),
+        &port_forward.log_path(),
+        kubectl::ready_timeout(),
+    )
+    .await?;
+
+    port_forward.pid = Some(pid);
+    Ok(port_forward)
+}
+
+impl From<PortForwardsList> for PortForwardsListCache {
+    fn from(value: PortForwardsList) -> Self {
+        let list = value
+            .list
+            .into_iter()
+            .map(|item| PortForward {
+                was_active: item.is_active(),
+                ..item
+            })
+            .collect();
+
+        Self {
+            list,
+            state: StateCache {
+                selected: value.state.selected(),
+            },
+            profiles: value.profiles,
+            profiles_state: StateCache {
+                selected: value.profiles_state.selected(),
+            },
+        }
+    }
+}
+
+impl PortForwardsList {
+    pub fn new(event_sender: EventSender) -> Self {
+        let mut state = ListState::default();
+        state.select(Some(1));
+
+        let mut profiles_state = ListState::default();
+        profiles_state.select(Some(0));
+
+        Self {
+            event_sender,
+            list: vec![],
+            state,
+            profiles: vec![],
+            profiles_state,
+            profile_name_input: None,
+            health_path_input: None,
+            last_probe: None,
+            env_template_input: None,
+            is_export_prompt_open: false,
+            export_path_input: None,
+            restore_on_start: false,
+            is_loading: false,
+        }
+    }
+
+    pub fn from_cache(value: PortForwardsListCache, event_sender: EventSender) -> Self {
+        let mut port_forwards = Self::from_live_cache(value, event_sender);
+
+        let list = std::mem::take(&mut port_forwards.list)
+            .into_iter()
+            .map(|item| {
+                if let Some(pid) = item.pid {
+                    // The PID may have been reused, e.g. after a reboot, only trust it when it
+                    // still runs this very forward.
+                    let is_active_port_forward = item.is_running();
+
+                    let (pid, state) = if is_active_port_forward {
+                        (Some(pid), ForwardState::Up)
+                    } else {
+                        (None, ForwardState::Inactive)
+                    };
+
+                    let mut item = PortForward { pid, state, ..item };
+                    item.watch_process(&port_forwards.event_sender);
+
+                    return item;
+                }
+
+                // A proxy keeps serving across context switches. Anything else without a process
+                // is not running, whatever it was doing when its context was left.
+                match item.proxy {
+                    Some(_) => item,
+                    None => PortForward {
+                        state: ForwardState::Inactive,
+                        ..item
+                    },
+                }
+            })
+            .collect();
+
+        port_forwards.list = list;
+        port_forwards
+    }
+
+    /// Takes the state of a context that is not shown as is, its forwards are still monitored
+    /// from when it was left.
+    pub fn from_live_cache(value: PortForwardsListCache, event_sender: EventSender) -> Self {
+        let mut state = ListState::default();
+        state.select(value.state.selected);
+
+        let mut profiles_state = ListState::default();
+        profiles_state.select(value.profiles_state.selected);
+
+        Self {
+            list: value.list,
+            state,
+            profiles: value.profiles,
+            profiles_state,
+            profile_name_input: None,
+            health_path_input: None,
+            last_probe: None,
+            env_template_input: None,
+            is_export_prompt_open: false,
+            export_path_input: None,
+            restore_on_start: false,
+            event_sender,
+            is_loading: false,
+        }
+    }
+
+    pub fn has_process(&self, pid: u32) -> bool {
+        self.list.iter().any(|item| item.pid == Some(pid))
+    }
+
+    /// Adds forwards found by `find_outside_port_forwards`, so they can be supervised, stopped
+    /// and restarted like our own. Returns how many were added.
+    pub fn adopt(&mut self, found: Vec<PortForward>) -> usize {
+        let mut count = 0;
+
+        for mut item in found {
+            // Adopted by an earlier, overlapping search.
+            if self.list.iter().any(|known| known.pid == item.pid) {
+                continue;
+            }
+
+            item.watch_process(&self.event_sender);
+            self.add_to_list(item);
+            count += 1;
+        }
+
+        count
+    }
+
+    pub fn add_to_list(&mut self, new_item: PortForward) {
+        // A relaunched forward replaces its inactive entry instead of duplicating it.
+        self.list.retain(|item| !item.is_same_forward(&new_item));
+
+        if new_item.is_active() {
+            self.list.insert(0, new_item);
+        } else {
+            self.list.push(new_item);
+        }
+    }
+
+    /// Local ports of the forwards that are running or about to run again.
+    pub fn forwarded_ports(&self) -> ForwardedPorts {
+        self.list
+            .iter()
+            .filter(|item| item.is_active())
+            .flat_map(|item| {
+                let owner = format!("{} {}", item.target(), item.ports_label());
+
+                item.port_mappings()
+                    .into_iter()
+                    .map(move |ports| (ports.local_port, owner.clone()))
+            })
+            .collect()
+    }
+
+    /// Stops every active forward holding one of the local ports `port_forward` needs.
+    pub fn stop_conflicting(&mut self, port_forward: &PortForward) {
+        let local_ports: Vec<u16> = port_forward
+            .port_mappings()
+            .iter()
+            .map(|ports| ports.local_port)
+            .collect();
+
+        while let Some(index) = self.list.iter().position(|item| {
+            item.is_active()
+                && item
+                    .port_mappings()
+                    .iter()
+                    .any(|ports| local_ports.contains(&ports.local_port))
+        }) {
+            if !self.stop_item(index) {
+                return;
+            }
+        }
+    }
+
+    pub fn set_loading(&mut self, is_loading: bool) {
+        self.is_loading = is_loading;
+    }
+
+    pub fn set_restore_on_start(&mut self, restore_on_start: bool) {
+        self.restore_on_start = restore_on_start;
+    }
+
+    fn find_mut(&mut self, port_forward: &PortForward) -> Option<&mut PortForward> {
+        self.list
+            .iter_mut()
+            .find(|item| item.is_same_forward(port_forward))
+    }
+
+    pub fn mark_starting(&mut self, port_forward: &PortForward) {
+        if let Some(item) = self.find_mut(port_forward)
+            && item.state != ForwardState::Reconnecting
+        {
+            item.state = ForwardState::Starting;
+        }
+    }
+
+    /// Starts listening for a proxied forward, kubectl itself only runs once a connection arrives.
+    pub fn start_proxy(&mut self, port_forward: PortForward) {
+        match Proxy::start(&port_forward, self.event_sender.clone()) {
+            Ok(proxy) => {
+                self.event_sender
+                    .send(AppEvent::ShowNotification(Log::Info(format!(
+                        "Proxying {} {}, kubectl starts on the first connection",
+                        port_forward.target(),
+                        port_forward.ports_label()
+                    ))));
+
+                self.add_to_list(PortForward {
+                    proxy: Some(proxy),
+                    state: ForwardState::Up,
+                    restart_attempt: 0,
+                    ..port_forward
+                });
+            }
+            Err(err) => {
+                if let Some(item) = self.find_mut(&port_forward) {
+                    item.state = ForwardState::Failed;
+                }
+
+                self.event_sender
+                    .send(AppEvent::ShowNotification(Log::Error(err.to_string())));
+            }
+        }
+    }
+
+    /// Moves the forward between the built-in proxy and a plain kubectl forward, restarting it if
+    /// it was running.
+    fn toggle_proxy(&mut self, index: usize) {
+        let was_active = self.list[index].is_active();
+        let port_forward = self.list[index].clone();
+
+        if !self.stop_item(index) {
+            return;
+        }
+
+        let Some(item) = self.find_mut(&port_forward) else {
+            return;
+        };
+
+        item.proxied = !item.proxied;
+
+        let message = if item.proxied {
+            "now runs through the built-in proxy"
+        } else {
+            "now runs kubectl directly"
+        };
+
+        let message = format!(
+            "Port forward {} {} {message}",
+            item.target(),
+            item.ports_label()
+        );
+        let item = item.clone();
+
+        self.event_sender
+            .send(AppEvent::ShowNotification(Log::Info(message)));
+
+        if was_active {
+            // Gives kubectl time to release the local ports before they are bound again.
+            self.event_sender.send(AppEvent::ReplacePortForward(item));
+        }
+    }
+
+    /// Returns the forward to restart and the delay to wait before doing so.
+    pub fn handle_port_forward_exited(&mut self, pid: u32) -> Option<(PortForward, Duration)> {
+        let item = self.list.iter_mut().find(|item| item.pid == Some(pid))?;
+
+        item.pid = None;
+        item.monitor = None;
+        item.restarts += 1;
+
+        // A forward that dies right after starting backs off further, one that ran for a while
+        // starts over.
+        let was_stable = item
+            .up_since
+            .take()
+            .is_some_and(|since| since.elapsed() >= MIN_STABLE_UPTIME);
+
+        item.restart_attempt = if was_stable {
+            0
+        } else {
+            item.restart_attempt + 1
+        };
+
+        if item.restart_attempt >= MAX_RESTART_ATTEMPTS {
+            item.state = ForwardState::Failed;
+
+            self.event_sender
+                .send(AppEvent::ShowNotification(Log::Error(format!(
+                    "Gave up restarting port forward {} {}, it keeps exiting",
+                    item.target(),
+                    item.ports_label()
+                ))));
+
+            return None;
+        }
+
+        item.state = ForwardState::Reconnecting;
+
+        self.event_sender
+            .send(AppEvent::ShowNotification(Log::Warning(format!(
+                "Port forward {} {} exited, reconnecting",
+                item.target(),
+                item.ports_label()
+            ))));
+
+        Some((item.clone(), item.restart_delay()))
+    }
+
+    /// Returns the forward to retry and the delay to wait when a supervised restart failed.
+    pub fn handle_port_forward_started(
+        &mut self,
+        port_forward: PortForward,
+        result: AppResult<PortForward>,
+    ) -> Option<(PortForward, Duration)> {
+        let was_reconnecting = port_forward.state == ForwardState::Reconnecting;
+
+        match result {
+            Ok(mut started) => {
+                let message = if was_reconnecting {
+                    format!(
+                        "Port forward {} {} reconnected",
+                        started.target(),
+                        started.ports_label()
+                    )
+                } else {
+                    format!("Forwarding {} {}", started.target(), started.ports_label())
+                };
+
+                self.event_sender
+                    .send(AppEvent::ShowNotification(Log::Info(message)));
+
+                // A reconnected forward keeps its attempts until it has proven stable.
+                if !was_reconnecting {
+                    started.restart_attempt = 0;
+                }
+
+                started.state = ForwardState::Up;
+                started.watch_process(&self.event_sender);
+
+                self.add_to_list(started);
+                None
+            }
+            Err(err) => {
+                let event_sender = self.event_sender.clone();
+                let item = self.find_mut(&port_forward);
+
+                let Some(item) = item else {
+                    event_sender.send(AppEvent::ShowNotification(Log::Error(err.to_string())));
+                    return None;
+                };
+
+                if !was_reconnecting || item.state != ForwardState::Reconnecting {
+                    item.state = ForwardState::Failed;
+                    event_sender.send(AppEvent::ShowNotification(Log::Error(err.to_string())));
+                    return None;
+                }
+
+                item.restart_attempt += 1;
+
+                if item.restart_attempt >= MAX_RESTART_ATTEMPTS {
+                    item.state = ForwardState::Failed;
+
+                    event_sender.send(AppEvent::ShowNotification(Log::Error(format!(
+                        "Gave up restarting port forward {} {}: {err}",
+                        item.target(),
+                        item.ports_label()
+                    ))));
+
+                    return None;
+                }
+
+                Some((item.clone(), item.restart_delay()))
+            }
+        }
+    }
+
+    pub fn draw(&mut self, area: Rect, frame: &mut Frame, is_focused: bool) {
+        let namespaces_list_items: Vec<ListItem> = self
+            .list
+            .iter()
+            .map(|item| {
+                let (marker, color) = match (item.state, &item.proxy) {
+                    (ForwardState::Up, Some(proxy)) => match proxy.backend_state() {
+                        BackendState::Idle => ("◎", Color::Cyan),
+                        BackendState::Starting => ("◌", Color::Yellow),
+                        BackendState::Up => ("●", Color::Green),
+                    },
+                    (ForwardState::Inactive, _) => ("○", Color::Reset),
+                    (ForwardState::Starting, _) => ("◌", Color::Yellow),
+                    (ForwardState::Up, None) => ("●", Color::Green),
+                    (ForwardState::Reconnecting, _) => ("↻", Color::Yellow),
+                    (ForwardState::Failed, _) => ("✗", Color::Red),
+                };
+
+                let mark = if item.is_marked { "+" } else { " " };
+
+                let mut spans = vec![
+                    Span::from(format!(
+                        "{mark}{marker} {} {}",
+                        item.target(),
+                        item.ports_label()
+                    ))
+                    .style(Style::default().fg(color)),
+                ];
+
+                if item.proxied {
+                    spans.push(Span::from(" ⇄").style(Style::default().fg(Color::Cyan)));
+                }
+
+                if item.restarts > 0 {
+                    spans.push(
+                        Span::from(format!(" ↻{}", item.restarts))
+                            .style(Style::default().fg(Color::DarkGray)),
+                    );
+                }
+
+                match &item.health {
+                    Some(Health::Healthy(latency)) => spans.push(
+                        Span::from(format!(" {}ms", latency.as_millis()))
+                            .style(Style::default().fg(Color::DarkGray)),
+                    ),
+                    Some(Health::Unhealthy(_)) => {
+                        spans.push(Span::from(" unhealthy").style(Style::default().fg(Color::Red)))
+                    }
+                    None => {}
+                }
+
+                let Some(proxy) = &item.proxy else {
+                    return ListItem::new(Line::from(spans));
+                };
+
+                let stats = Line::from(vec![
+                    Span::from(format!("    {} ", proxy.stats().summary()))
+                        .style(Style::default().fg(Color::DarkGray)),
+                    Span::from(proxy.stats().sparkline()).style(Style::default().fg(Color::Cyan)),
+                ]);
+
+                ListItem::new(vec![Line::from(spans), stats])
+            })
+            .collect();
+
+        let title = if self.restore_on_start {
+            "Port Forwards (restored on start)"
+        } else {
+            "Port Forwards"
+        };
+
+        let title = loading_title(title, self.is_loading);
+        let block = build_block(title.as_str(), is_focused);
+
+        let list = List::new(namespaces_list_items)
+            .block(block)
+            .highlight_style(get_highlight_style());
+
+        frame.render_stateful_widget(list, area, &mut self.state);
+        self.draw_profile_name_input(frame);
+        self.draw_health_path_input(frame);
+        self.draw_export_inputs(frame);
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) {
+        if self.handle_profile_name_input(key)
+            || self.handle_health_path_input(key)
+            || self.handle_export_inputs(key)
+        {
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => self.select_prev(),
+            KeyCode::Char('p') | KeyCode::Enter => {
+                if let Some(selected) = self.selected_index() {
+                    self.restart_item(selected);
+                }
+            }
+            KeyCode::Char('s') => {
+                if let Some(selected) = self.selected_index() {
+                    self.stop_item(selected);
+                }
+            }
+            KeyCode::Char('d') => {
+                if let Some(selected) = self.selected_index() {
+                    self.delete_item(selected);
+                }
+            }
+            KeyCode::Char('a') => self.event_sender.send(AppEvent::AdoptPortForwards),
+            KeyCode::Char('h') => {
+                if let Some(selected) = self.selected_index() {
+                    self.open_health_path_input(selected);
+                }
+            }
+            KeyCode::Char('v') => {
+                if let Some(selected) = self.selected_index() {
+                    self.open_env_template_input(selected);
+                }
+            }
+            KeyCode::Char('e') => self.is_export_prompt_open = true,
+            KeyCode::Char('R') => self.event_sender.send(AppEvent::ToggleRestorePortForwards),
+            KeyCode::Char('t') => {
+                if let Some(selected) = self.selected_index() {
+                    self.toggle_proxy(selected);
+                }
+            }
+            KeyCode::Char(' ') => {
+                if let Some(selected) = self.selected_index() {
+                    self.list[selected].is_marked = !self.list[selected].is_marked;
+                }
+            }
+            KeyCode::Char('P') => self.profile_name_input = Some(String::new()),
+            KeyCode::Char('l') => {
+                if let Some(selected) = self.selected_index() {
+                    let item = &self.list[selected];
+
+                    self.event_sender
+                        .send(AppEvent::OpenLogs(LogsSource::PortForward {
+                            name: format!("{} {}", item.target(), item.ports_label()),
+                            log_path: item.log_path(),
+                        }));
+                }
+            }
+            _ => {}
+        }
+
+        handle_general_keys(key, &self.event_sender);
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        self.state.selected().filter(|&i| i < self.list.len())
+    }
+
+    fn restart_item(&mut self, index: usize) {
+        let item = &self.list[index];
+
+        // A proxied forward holds its local ports without a process of its own.
+        if item.is_active() {
+            self.event_sender
+                .send(AppEvent::ShowNotification(Log::Info(format!(
+                    "Port forward {} {} is already running",
+                    item.target(),
+                    item.ports_label()
+                ))));
+            return;
+        }
+
+        self.event_sender.send(AppEvent::PortForward(item.clone()));
+    }
+
+    /// Kills the forward's process. Returns `false` when it could not be stopped.
+    fn kill_item(&mut self, index: usize) -> bool {
+        let item = &mut self.list[index];
+
+        // Stop supervising first so the exit is not mistaken for a crash.
+        item.monitor = None;
+        // Closes the proxy's listeners and stops its kubectl.
+        item.proxy = None;
+        item.health = None;
+
+        self.event_sender
+            .send(AppEvent::CancelJob(Job::PortForward {
+                local_port: item.local_port,
+            }));
+
+        let Some(pid) = item.pid else {
+            return true;
+        };
+
+        // Never signal a process that is not this forward anymore.
+        if !item.is_running() {
+            return true;
+        }
+
+        if let Err(err) = kubectl::stop_port_forward(pid) {
+            self.event_sender
+                .send(AppEvent::ShowNotification(Log::Error(err.to_string())));
+            return false;
+        }
+
+        // The monitor is gone, something still has to see the exit.
+        kubectl::reap_port_forward(pid);
+
+        true
+    }
+
+    /// Returns `false` when the forward could not be stopped.
+    fn stop_item(&mut self, index: usize) -> bool {
+        if self.list[index].state == ForwardState::Inactive {
+            return true;
+        }
+
+        if !self.kill_item(index) {
+            return false;
+        }
+
+        // Active forwards are kept on top, the stopped one moves down with the inactive ones.
+        let mut item = self.list.remove(index);
+        item.pid = None;
+        item.state = ForwardState::Inactive;
+        self.list.push(item);
+
+        true
+    }
+
+    fn delete_item(&mut self, index: usize) {
+        if !self.kill_item(index) {
+            return;
+        }
+
+        self.list.remove(index);
+
+        if self.list.is_empty() {
+            self.state.select(None);
+        } else if let Some(selected) = self.state.selected()
+            && selected >= self.list.len()
+        {
+            self.state.select(Some(self.list.len() - 1));
+        }
+    }
+
+    fn select_next(&mut self) {
+        if self.list.is_empty() {
+            return;
+        }
+
+        let i = match self.state.selected() {
+            Some(i) => {
+                if i == self.list.len() - 1 {
+                    0
+                } else {
+                    i + 1
+                }
+            }
+            None => 0,
+        };
+
+        self.state.select(Some(i));
+    }
+
+    fn select_prev(&mut self) {
+        if self.list.is_empty() {
+            return;
+        }
+
+        let i = match self.state.selected() {
+            Some(i) => {
+                if i == 0 {
+                    self.list.len() - 1
+                } else {
+                    i - 1
+                }
+            }
+            None => self.list.len() - 1,
+        };
+
+        self.state.select(Some(i));
+    }
+}
@@ -0,0 +1,257 @@
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    crossterm::event::KeyEvent,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::app::{
+    common::{build_block, draw_text_input, get_highlight_style, handle_general_keys},
+    events::{AppEvent, Log},
+    side_bar::port_forwards::{ForwardState, PortForward, PortForwardsList},
+};
+
+/// A named set of forwards that are started and stopped together. Pod forwards keep their
+/// workload selector and services/deployments are resolved by kubectl, so a profile keeps working
+/// after the pods it was saved with are gone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortForwardProfile {
+    pub name: String,
+    pub forwards: Vec<PortForward>,
+}
+
+impl PortForwardsList {
+    /// How many forwards of the profile are up, out of how many it has.
+    fn profile_status(&self, profile: &PortForwardProfile) -> (usize, usize) {
+        let up = profile
+            .forwards
+            .iter()
+            .filter(|forward| {
+                self.list
+                    .iter()
+                    .any(|item| item.is_same_forward(forward) && item.state == ForwardState::Up)
+            })
+            .count();
+
+        (up, profile.forwards.len())
+    }
+
+    /// Saves the marked forwards, or all of them when none is marked, under `name`.
+    /// A profile with the same name is replaced.
+    fn save_profile(&mut self, name: String) {
+        let has_marked = self.list.iter().any(|item| item.is_marked);
+
+        let forwards: Vec<PortForward> = self
+            .list
+            .iter()
+            .filter(|item| !has_marked || item.is_marked)
+            .map(PortForward::config_copy)
+            .collect();
+
+        if forwards.is_empty() {
+            self.event_sender
+                .send(AppEvent::ShowNotification(Log::Warning(
+                    "There are no port forwards to save".to_string(),
+                )));
+            return;
+        }
+
+        for item in self.list.iter_mut() {
+            item.is_marked = false;
+        }
+
+        let message = format!("Saved profile {name} with {} forwards", forwards.len());
+        let profile = PortForwardProfile { name, forwards };
+
+        match self.profiles.iter().position(|p| p.name == profile.name) {
+            Some(index) => self.profiles[index] = profile,
+            None => {
+                self.profiles.push(profile);
+                self.profiles.sort_by(|a, b| a.name.cmp(&b.name));
+            }
+        }
+
+        self.event_sender
+            .send(AppEvent::ShowNotification(Log::Info(message)));
+    }
+
+    fn start_profile(&mut self, index: usize) {
+        let Some(profile) = self.profiles.get(index) else {
+            return;
+        };
+
+        for forward in &profile.forwards {
+            let is_active = self
+                .list
+                .iter()
+                .any(|item| item.is_same_forward(forward) && item.is_active());
+
+            if !is_active {
+                self.event_sender
+                    .send(AppEvent::PortForward(forward.clone()));
+            }
+        }
+    }
+
+    fn stop_profile(&mut self, index: usize) {
+        let Some(profile) = self.profiles.get(index).cloned() else {
+            return;
+        };
+
+        for forward in &profile.forwards {
+            if let Some(index) = self
+                .list
+                .iter()
+                .position(|item| item.is_same_forward(forward))
+            {
+                self.stop_item(index);
+            }
+        }
+    }
+
+    fn delete_profile(&mut self, index: usize) {
+        if index >= self.profiles.len() {
+            return;
+        }
+
+        self.profiles.remove(index);
+
+        if self.profiles.is_empty() {
+            self.profiles_state.select(None);
+        } else if let Some(selected) = self.profiles_state.selected()
+            && selected >= self.profiles.len()
+        {
+            self.profiles_state.select(Some(self.profiles.len() - 1));
+        }
+    }
+
+    pub fn draw_profiles(&mut self, area: Rect, frame: &mut Frame, is_focused: bool) {
+        let items: Vec<ListItem> = self
+            .profiles
+            .iter()
+            .map(|profile| {
+                let (up, total) = self.profile_status(profile);
+
+                let (marker, color) = match up {
+                    0 => ("○", Color::Reset),
+                    up if up == total => ("●", Color::Green),
+                    _ => ("◐", Color::Yellow),
+                };
+
+                ListItem::new(Line::from(vec![
+                    Span::from(format!("{marker} {}", profile.name))
+                        .style(Style::default().fg(color)),
+                    Span::from(format!(" {up}/{total} up"))
+                        .style(Style::default().fg(Color::DarkGray)),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(build_block("Profiles", is_focused))
+            .highlight_style(get_highlight_style());
+
+        frame.render_stateful_widget(list, area, &mut self.profiles_state);
+    }
+
+    pub(super) fn draw_profile_name_input(&self, frame: &mut Frame) {
+        let Some(name) = &self.profile_name_input else {
+            return;
+        };
+
+        let has_marked = self.list.iter().any(|item| item.is_marked);
+        let title = if has_marked {
+            "Save marked forwards as profile"
+        } else {
+            "Save all forwards as profile"
+        };
+
+        draw_text_input(frame, title, name);
+    }
+
+    /// Returns `true` when the key was consumed by the profile name input.
+    pub(super) fn handle_profile_name_input(&mut self, key: KeyEvent) -> bool {
+        let Some(name) = &mut self.profile_name_input else {
+            return false;
+        };
+
+        match key.code {
+            KeyCode::Char(ch) => name.push(ch),
+            KeyCode::Backspace => {
+                name.pop();
+            }
+            KeyCode::Enter => {
+                let name = name.trim().to_string();
+                self.profile_name_input = None;
+
+                if !name.is_empty() {
+                    self.save_profile(name);
+                }
+            }
+            KeyCode::Esc => self.profile_name_input = None,
+            _ => {}
+        }
+
+        true
+    }
+
+    pub fn handle_profiles_key_event(&mut self, key: KeyEvent) {
+        let selected = self
+            .profiles_state
+            .selected()
+            .filter(|&i| i < self.profiles.len());
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.select_next_profile(),
+            KeyCode::Char('k') | KeyCode::Up => self.select_prev_profile(),
+            KeyCode::Char('p') | KeyCode::Enter => {
+                if let Some(selected) = selected {
+                    self.start_profile(selected);
+                }
+            }
+            KeyCode::Char('s') => {
+                if let Some(selected) = selected {
+                    self.stop_profile(selected);
+                }
+            }
+            KeyCode::Char('d') => {
+                if let Some(selected) = selected {
+                    self.delete_profile(selected);
+                }
+            }
+            _ => {}
+        }
+
+        handle_general_keys(key, &self.event_sender);
+    }
+
+    fn select_next_profile(&mut self) {
+        if self.profiles.is_empty() {
+            return;
+        }
+
+        let i = match self.profiles_state.selected() {
+            Some(i) if i + 1 < self.profiles.len() => i + 1,
+            _ => 0,
+        };
+
+        self.profiles_state.select(Some(i));
+    }
+
+    fn select_prev_profile(&mut self) {
+        if self.profiles.is_empty() {
+            return;
+        }
+
+        let i = match self.profiles_state.selected() {
+            Some(i) if i > 0 => i - 1,
+            _ => self.profiles.len() - 1,
+        };
+
+        self.profiles_state.select(Some(i));
+    }
+}
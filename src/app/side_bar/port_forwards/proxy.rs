@@ -0,0 +1,540 @@
+use std::{
+    collections::VecDeque,
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener as StdTcpListener},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use anyhow::Context;
+use futures::{
+    FutureExt,
+    future::{BoxFuture, Shared},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{
+        TcpListener, TcpStream,
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+    },
+    sync::Mutex as AsyncMutex,
+    task::JoinSet,
+    time::{MissedTickBehavior, interval, sleep},
+};
+
+use crate::{
+    app::{
+        events::{AppEvent, EventSender, Log},
+        side_bar::port_forwards::{PortForward, resolve_target_name},
+    },
+    error::{AppError, AppResult},
+    kubectl::{self, KubectlStream, PortMapping},
+};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+const HISTORY_LEN: usize = 20;
+const BUFFER_SIZE: usize = 16 * 1024;
+/// Pause after a failed `accept`. Errors like running out of file descriptors persist, retrying
+/// right away would spin.
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+const SPARKLINE_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Serves a forward's local ports from inside the app and relays every connection to a kubectl
+/// process forwarding ephemeral ports. kubectl is only started once the first connection arrives
+/// and again on the next one after it exited. Dropping the last clone stops both.
+#[derive(Debug, Clone)]
+pub struct Proxy {
+    stats: Arc<ProxyStats>,
+    backend: Arc<AsyncMutex<Backend>>,
+    _task: KubectlStream,
+}
+
+/// Traffic relayed by a proxy since it was started.
+#[derive(Debug, Default)]
+pub struct ProxyStats {
+    connections: AtomicU64,
+    open_connections: AtomicU64,
+    /// From the local clients to the pod.
+    bytes_sent: AtomicU64,
+    /// From the pod back to the local clients.
+    bytes_received: AtomicU64,
+    errors: AtomicU64,
+    /// Bytes relayed per `SAMPLE_INTERVAL`, oldest first.
+    history: Mutex<VecDeque<u64>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendState {
+    /// Waiting for the first connection.
+    Idle,
+    Starting,
+    Up,
+}
+
+/// kubectl's pid and the ephemeral ports it listens on, or why it did not start.
+type Launch = Shared<BoxFuture<'static, Result<(u32, Vec<PortMapping>), String>>>;
+
+struct Backend {
+    port_forward: PortForward,
+    pid: Option<u32>,
+    /// Ephemeral local ports kubectl listens on, mapped to the forward's app ports.
+    ports: Vec<PortMapping>,
+    /// kubectl being started, awaited by every connection that arrives meanwhile.
+    launch: Option<Launch>,
+}
+
+impl std::fmt::Debug for Backend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Backend")
+            .field("port_forward", &self.port_forward)
+            .field("pid", &self.pid)
+            .field("ports", &self.ports)
+            .field("is_launching", &self.launch.is_some())
+            .finish()
+    }
+}
+
+impl Proxy {
+    /// Binds the forward's local ports right away, so a busy port is reported immediately.
+    pub fn start(port_forward: &PortForward, event_sender: EventSender) -> AppResult<Self> {
+        let mut listeners = vec![];
+
+        for ports in port_forward.port_mappings() {
+            let listener = bind(Ipv4Addr::LOCALHOST.into(), ports.local_port).map_err(|err| {
+                AppError::PortForwardError(anyhow::anyhow!(
+                    "Local port {} is not available: {err}",
+                    ports.local_port
+                ))
+            })?;
+
+            listeners.push((listener, ports.app_port));
+
+            // Like kubectl, also serve clients resolving localhost to ::1 where IPv6 is available.
+            if let Ok(listener) = bind(Ipv6Addr::LOCALHOST.into(), ports.local_port) {
+                listeners.push((listener, ports.app_port));
+            }
+        }
+
+        let backend_port_forward = PortForward {
+            proxied: true,
+            ..port_forward.config_copy()
+        };
+
+        let stats = Arc::new(ProxyStats::default());
+        let backend = Arc::new(AsyncMutex::new(Backend {
+            port_forward: backend_port_forward,
+            pid: None,
+            ports: vec![],
+            launch: None,
+        }));
+
+        let task_stats = stats.clone();
+        let task_backend = backend.clone();
+
+        let task = tokio::spawn(async move {
+            let mut tasks = JoinSet::new();
+
+            for (listener, app_port) in listeners {
+                tasks.spawn(accept_connections(
+                    listener,
+                    app_port,
+                    task_backend.clone(),
+                    task_stats.clone(),
+                    event_sender.clone(),
+                ));
+            }
+
+            tasks.spawn(sample_traffic(task_stats));
+
+            while tasks.join_next().await.is_some() {}
+        });
+
+        Ok(Self {
+            stats,
+            backend,
+            _task: KubectlStream::from_task(task),
+        })
+    }
+
+    pub fn stats(&self) -> &ProxyStats {
+        &self.stats
+    }
+
+    /// Ephemeral port kubectl listens on for `app_port`, while it runs.
+    pub fn backend_port(&self, app_port: u16) -> Option<u16> {
+        let backend = self.backend.try_lock().ok()?;
+
+        backend
+            .ports
+            .iter()
+            .find(|ports| ports.app_port == app_port)
+            .map(|ports| ports.local_port)
+    }
+
+    pub fn backend_state(&self) -> BackendState {
+        match self.backend.try_lock() {
+            Ok(backend) if backend.launch.is_some() => BackendState::Starting,
+            Ok(backend) if backend.pid.is_some_and(kubectl::is_process_alive) => BackendState::Up,
+            Ok(_) => BackendState::Idle,
+            // Only held for a moment by a connection looking up kubectl's port.
+            Err(_) => BackendState::Up,
+        }
+    }
+}
+
+impl ProxyStats {
+    /// `12 conns (2 open) ↑1.2 KB ↓3.4 MB`, with the errors when there were any.
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} conns ({} open) ↑{} ↓{}",
+            self.connections.load(Ordering::Relaxed),
+            self.open_connections.load(Ordering::Relaxed),
+            format_bytes(self.bytes_sent.load(Ordering::Relaxed)),
+            format_bytes(self.bytes_received.load(Ordering::Relaxed)),
+        );
+
+        let errors = self.errors.load(Ordering::Relaxed);
+
+        if errors > 0 {
+            summary.push_str(format!(" {errors} errors").as_str());
+        }
+
+        summary
+    }
+
+    /// Recent throughput, scaled to the busiest sample.
+    pub fn sparkline(&self) -> String {
+        let Ok(history) = self.history.lock() else {
+            return String::new();
+        };
+
+        let max = history.iter().copied().max().unwrap_or(0);
+
+        history
+            .iter()
+            .map(|&bytes| {
+                let level = match max {
+                    0 => 0,
+                    max => (bytes * (SPARKLINE_BARS.len() as u64 - 1)).div_ceil(max) as usize,
+                };
+
+                SPARKLINE_BARS[level]
+            })
+            .collect()
+    }
+
+    fn total_bytes(&self) -> u64 {
+        self.bytes_sent.load(Ordering::Relaxed) + self.bytes_received.load(Ordering::Relaxed)
+    }
+}
+
+impl Backend {
+    fn port(&self, app_port: u16) -> AppResult<u16> {
+        self.ports
+            .iter()
+            .find(|ports| ports.app_port == app_port)
+            .map(|ports| ports.local_port)
+            .ok_or_else(|| {
+                AppError::PortForwardError(anyhow::anyhow!("Port {app_port} is not forwarded"))
+            })
+    }
+
+    /// The start of kubectl in progress, or a new one.
+    fn launch(&mut self) -> Launch {
+        if let Some(launch) = &self.launch {
+            return launch.clone();
+        }
+
+        self.pid = None;
+
+        let launch = launch(self.port_forward.clone()).boxed().shared();
+        self.launch = Some(launch.clone());
+
+        launch
+    }
+
+    /// Takes the result of `launch`, unless a connection that awaited it too already did.
+    fn finish_launch(
+        &mut self,
+        launch: &Launch,
+        result: Result<(u32, Vec<PortMapping>), String>,
+        event_sender: &EventSender,
+    ) -> AppResult<()> {
+        if !self
+            .launch
+            .as_ref()
+            .is_some_and(|current| current.ptr_eq(launch))
+        {
+            return result
+                .map(|_| ())
+                .map_err(|err| AppError::PortForwardError(anyhow::anyhow!(err)));
+        }
+
+        self.launch = None;
+
+        let log = match &result {
+            Ok(_) => Log::Info(format!(
+                "Started kubectl for proxied forward {} {}",
+                self.port_forward.target(),
+                self.port_forward.ports_label()
+            )),
+            Err(err) => Log::Error(format!(
+                "Proxied forward {} {} could not start kubectl: {err}",
+                self.port_forward.target(),
+                self.port_forward.ports_label()
+            )),
+        };
+
+        event_sender.send(AppEvent::ShowNotification(log));
+
+        let (pid, ports) =
+            result.map_err(|err| AppError::PortForwardError(anyhow::anyhow!(err)))?;
+        self.pid = Some(pid);
+        self.ports = ports;
+
+        Ok(())
+    }
+}
+
+async fn launch(port_forward: PortForward) -> Result<(u32, Vec<PortMapping>), String> {
+    let result: AppResult<_> = async {
+        let ports = port_forward
+            .port_mappings()
+            .iter()
+            .map(|ports| Ok(PortMapping::new(ephemeral_port()?, ports.app_port)))
+            .collect::<AppResult<Vec<_>>>()?;
+
+        let target_name = resolve_target_name(&port_forward).await?;
+
+        // The log is kept under the forward's own ports, kubectl's ephemeral ones change on every
+        // start.
+        let pid = kubectl::start_port_forward(
+            port_forward.context.as_str(),
+            port_forward.namespace.as_str(),
+            port_forward.kind.target(target_name.as_str()).as_str(),
+            &ports,
+            &port_forward.log_path(),
+            kubectl::ready_timeout(),
+        )
+        .await?;
+
+        Ok((pid, ports))
+    }
+    .await;
+
+    // Every connection waiting for the start gets the result, errors are not `Clone`.
+    result.map_err(|err| err.to_string())
+}
+
+/// Local port of the running kubectl that forwards to `app_port`, starting kubectl first if it
+/// is not running. The lock is only held to look the port up, not while kubectl starts.
+async fn local_port(
+    backend: &AsyncMutex<Backend>,
+    app_port: u16,
+    event_sender: &EventSender,
+) -> AppResult<u16> {
+    let launch = {
+        let mut backend = backend.lock().await;
+
+        if backend.launch.is_none() && backend.pid.is_some_and(kubectl::is_process_alive) {
+            // Every connection adds to kubectl's log, and nothing else watches a proxy's kubectl.
+            kubectl::rotate_port_forward_log(&backend.port_forward.log_path());
+
+            return backend.port(app_port);
+        }
+
+        backend.launch()
+    };
+
+    let result = launch.clone().await;
+
+    let mut backend = backend.lock().await;
+    backend.finish_launch(&launch, result, event_sender)?;
+    backend.port(app_port)
+}
+
+impl Drop for Backend {
+    fn drop(&mut self) {
+        if let Some(pid) = self.pid
+            && kubectl::is_process_alive(pid)
+            && kubectl::stop_port_forward(pid).is_ok()
+        {
+            kubectl::reap_port_forward(pid);
+        }
+    }
+}
+
+fn bind(ip: IpAddr, port: u16) -> io::Result<TcpListener> {
+    let listener = StdTcpListener::bind(SocketAddr::new(ip, port))?;
+    listener.set_nonblocking(true)?;
+
+    TcpListener::from_std(listener)
+}
+
+/// A local port that is free right now, for kubectl to listen on.
+fn ephemeral_port() -> AppResult<u16> {
+    let listener = StdTcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+        .and_then(|listener| listener.local_addr())
+        .context("Failed to find a free local port")
+        .map_err(AppError::PortForwardError)?;
+
+    Ok(listener.port())
+}
+
+async fn accept_connections(
+    listener: TcpListener,
+    app_port: u16,
+    backend: Arc<AsyncMutex<Backend>>,
+    stats: Arc<ProxyStats>,
+    event_sender: EventSender,
+) {
+    let mut connections = JoinSet::new();
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((client, _)) => {
+                    stats.connections.fetch_add(1, Ordering::Relaxed);
+
+                    connections.spawn(relay(
+                        client,
+                        app_port,
+                        backend.clone(),
+                        stats.clone(),
+                        event_sender.clone(),
+                    ));
+                }
+                Err(_) => {
+                    stats.errors.fetch_add(1, Ordering::Relaxed);
+                    sleep(ACCEPT_ERROR_BACKOFF).await;
+                }
+            },
+
+            // Reap finished connections, so the set only holds the open ones.
+            Some(_) = connections.join_next() => {}
+        }
+    }
+}
+
+async fn relay(
+    client: TcpStream,
+    app_port: u16,
+    backend: Arc<AsyncMutex<Backend>>,
+    stats: Arc<ProxyStats>,
+    event_sender: EventSender,
+) {
+    stats.open_connections.fetch_add(1, Ordering::Relaxed);
+
+    let local_port = local_port(&backend, app_port, &event_sender).await;
+
+    let result = match local_port {
+        Ok(local_port) => relay_to(client, local_port, &stats).await,
+        Err(_) => Err(io::ErrorKind::NotConnected.into()),
+    };
+
+    if result.is_err() {
+        stats.errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    stats.open_connections.fetch_sub(1, Ordering::Relaxed);
+}
+
+async fn relay_to(client: TcpStream, local_port: u16, stats: &ProxyStats) -> io::Result<()> {
+    let upstream = TcpStream::connect((Ipv4Addr::LOCALHOST, local_port)).await?;
+
+    let (mut client_read, mut client_write) = client.into_split();
+    let (mut upstream_read, mut upstream_write) = upstream.into_split();
+
+    let (sent, received) = tokio::join!(
+        pipe(&mut client_read, &mut upstream_write, &stats.bytes_sent),
+        pipe(&mut upstream_read, &mut client_write, &stats.bytes_received),
+    );
+
+    sent.and(received)
+}
+
+/// Copies until EOF, counting the bytes as they go so the stats are live.
+async fn pipe(
+    reader: &mut OwnedReadHalf,
+    writer: &mut OwnedWriteHalf,
+    counter: &AtomicU64,
+) -> io::Result<()> {
+    let mut buffer = vec![0; BUFFER_SIZE];
+
+    let result = async {
+        loop {
+            let read = reader.read(&mut buffer).await?;
+
+            if read == 0 {
+                return Ok(());
+            }
+
+            writer.write_all(&buffer[..read]).await?;
+            counter.fetch_add(read as u64, Ordering::Relaxed);
+        }
+    }
+    .await;
+
+    // Passes the hang up on, also after an error, so the other direction ends as well instead of
+    // waiting on a half closed connection.
+    let shutdown = writer.shutdown().await;
+
+    match result.and(shutdown) {
+        // Either side hanging up is how connections usually end.
+        Err(err)
+            if matches!(
+                err.kind(),
+                io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::BrokenPipe
+                    | io::ErrorKind::NotConnected
+            ) =>
+        {
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+async fn sample_traffic(stats: Arc<ProxyStats>) {
+    let mut tick = interval(SAMPLE_INTERVAL);
+    tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    let mut last_total = 0;
+
+    loop {
+        tick.tick().await;
+
+        let total = stats.total_bytes();
+
+        if let Ok(mut history) = stats.history.lock() {
+            history.push_back(total - last_total);
+
+            if history.len() > HISTORY_LEN {
+                history.pop_front();
+            }
+        }
+
+        last_total = total;
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
@@ -0,0 +1,90 @@
+use crate::{
+    app::{
+        events::{AppEvent, Log},
+        side_bar::port_forwards::{ForwardState, PortForward, PortForwardsList, Proxy},
+    },
+    error::AppResult,
+};
+
+impl PortForwardsList {
+    /// Forwards that were active when the session was saved but are not anymore, e.g. after a
+    /// reboot. They are shown as starting until `handle_session_restored` gets their results.
+    pub fn session_forwards(&mut self) -> Vec<PortForward> {
+        self.list
+            .iter_mut()
+            .filter(|item| item.was_active && !item.is_active())
+            .map(|item| {
+                item.state = ForwardState::Starting;
+
+                PortForward {
+                    restarts: item.restarts,
+                    state: ForwardState::Starting,
+                    ..item.config_copy()
+                }
+            })
+            .collect()
+    }
+
+    /// Takes the launched session forwards, binding the proxied ones now, and sums up what came
+    /// back in a single notification.
+    pub fn handle_session_restored(&mut self, results: Vec<(PortForward, AppResult<PortForward>)>) {
+        let total = results.len();
+        let context = results
+            .first()
+            .map(|(port_forward, _)| port_forward.context.clone())
+            .unwrap_or_default();
+        let mut failed = vec![];
+
+        for (port_forward, result) in results {
+            let result = result.and_then(|started| {
+                if !started.proxied {
+                    return Ok(started);
+                }
+
+                Proxy::start(&started, self.event_sender.clone()).map(|proxy| PortForward {
+                    proxy: Some(proxy),
+                    ..started
+                })
+            });
+
+            match result {
+                Ok(mut started) => {
+                    started.state = ForwardState::Up;
+                    started.restart_attempt = 0;
+                    started.watch_process(&self.event_sender);
+
+                    self.add_to_list(started);
+                }
+                Err(err) => {
+                    if let Some(item) = self.find_mut(&port_forward) {
+                        item.state = ForwardState::Failed;
+                    }
+
+                    // kubectl's output can span many lines, the first one names the problem.
+                    let err = err.to_string();
+                    let reason = err.lines().next().unwrap_or_default();
+
+                    failed.push(format!(
+                        "{} {} ({reason})",
+                        port_forward.target(),
+                        port_forward.ports_label()
+                    ));
+                }
+            }
+        }
+
+        let log = if failed.is_empty() {
+            Log::Info(format!(
+                "Restored {total} port forwards of the last session in {context}"
+            ))
+        } else {
+            Log::Warning(format!(
+                "Restored {} of {total} port forwards of the last session in {context}, failed: {}",
+                total - failed.len(),
+                failed.join(", ")
+            ))
+        };
+
+        self.event_sender.send(AppEvent::ShowNotification(log));
+    }
+}
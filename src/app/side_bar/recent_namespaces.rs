@@ -56,6 +56,8 @@ impl RecentNamespacesList {
     }
 
     pub fn handle_key_event(&mut self, key: KeyEvent) {
+        let is_filtering = self.recent_namespaces_list.is_filtering();
+
         if let Some(list_event) = self.recent_namespaces_list.handle_key(key) {
             match list_event {
                 ListEvent::Quit => {
@@ -68,6 +70,8 @@ impl RecentNamespacesList {
         }
 
         // TODO: handle highlight borders on focused windows
-        handle_general_keys(key, &self.event_sender);
+        if !is_filtering {
+            handle_general_keys(key, &self.event_sender);
+        }
     }
 }
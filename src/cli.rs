@@ -0,0 +1,126 @@
+use crate::{
+    app::headless::ExportFormat,
+    error::{AppError, AppResult},
+};
+
+pub const USAGE: &str = "\
+Usage:
+  pro-kube                                   start the terminal UI
+  pro-kube pf list [options]                 list saved port forwards
+  pro-kube pf start <local-port>... [options]
+  pro-kube pf stop <local-port>... [options]
+  pro-kube pf restart <local-port>... [options]
+  pro-kube pf export [<local-port>...] [options]
+
+Options:
+  --all              act on every saved port forward of the context
+  --context <name>   context to use, defaults to the one last used in the UI
+  --json             print machine readable JSON
+  --format <format>  export as env (default), shell or json
+
+Environment:
+  KUBERTUI_PORT_FORWARD_TIMEOUT   seconds kubectl gets to start a forward, 10 by default";
+
+/// What the binary was asked to do.
+pub enum Mode {
+    Tui,
+    Help,
+    PortForwards(PortForwardsCommand),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortForwardsAction {
+    List,
+    Start,
+    Stop,
+    Restart,
+    Export,
+}
+
+/// `pro-kube pf ...`. Forwards are selected by their local port, the one thing that is unique
+/// among the forwards of a context.
+pub struct PortForwardsCommand {
+    pub action: PortForwardsAction,
+    pub local_ports: Vec<u16>,
+    pub all: bool,
+    pub context: Option<String>,
+    pub json: bool,
+    pub format: ExportFormat,
+}
+
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> AppResult<Mode> {
+    let mut args = args.into_iter().skip(1);
+
+    let Some(command) = args.next() else {
+        return Ok(Mode::Tui);
+    };
+
+    match command.as_str() {
+        "pf" | "port-forward" => {}
+        "-h" | "--help" | "help" => return Ok(Mode::Help),
+        _ => return Err(usage_error(format!("unknown command '{command}'"))),
+    }
+
+    let action = match args.next().as_deref() {
+        Some("list" | "ls") => PortForwardsAction::List,
+        Some("start") => PortForwardsAction::Start,
+        Some("stop") => PortForwardsAction::Stop,
+        Some("restart") => PortForwardsAction::Restart,
+        Some("export") => PortForwardsAction::Export,
+        Some(action) => return Err(usage_error(format!("unknown pf command '{action}'"))),
+        None => return Err(usage_error("missing pf command".to_string())),
+    };
+
+    let mut command = PortForwardsCommand {
+        action,
+        local_ports: vec![],
+        all: false,
+        context: None,
+        json: false,
+        format: ExportFormat::Env,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => command.json = true,
+            "--all" => command.all = true,
+            "--context" => {
+                let context = args
+                    .next()
+                    .ok_or_else(|| usage_error("--context needs a value".to_string()))?;
+
+                command.context = Some(context);
+            }
+            "--format" => {
+                let format = args
+                    .next()
+                    .ok_or_else(|| usage_error("--format needs a value".to_string()))?;
+
+                command.format = ExportFormat::parse(format.as_str())
+                    .ok_or_else(|| usage_error(format!("unknown export format '{format}'")))?;
+            }
+            "-h" | "--help" => return Ok(Mode::Help),
+            _ => match arg.parse::<u16>() {
+                Ok(port) if port > 0 => command.local_ports.push(port),
+                _ => return Err(usage_error(format!("invalid local port '{arg}'"))),
+            },
+        }
+    }
+
+    let selects_by_default = matches!(
+        action,
+        PortForwardsAction::List | PortForwardsAction::Export
+    );
+
+    if !selects_by_default && command.local_ports.is_empty() && !command.all {
+        return Err(usage_error(
+            "select forwards by local port or pass --all".to_string(),
+        ));
+    }
+
+    Ok(Mode::PortForwards(command))
+}
+
+fn usage_error(message: String) -> AppError {
+    AppError::GeneralError(anyhow::anyhow!("{message}\n\n{USAGE}"))
+}
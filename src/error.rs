@@ -3,11 +3,16 @@ use std::io;
 use anyhow::Error;
 use thiserror::Error;
 
+use crate::kubectl::PortForwardFailure;
+
 #[derive(Error, Debug)]
 pub enum AppError {
     #[error("failed to forward ports: {0}")]
     PortForwardError(Error),
 
+    #[error("failed to forward ports: {0}")]
+    PortForwardFailed(#[from] PortForwardFailure),
+
     #[error("failed to save/retrieve cache: {0}")]
     CacheError(Error),
 
@@ -1,3 +1,5 @@
+use std::{fs::Permissions, os::unix::fs::PermissionsExt};
+
 use anyhow::Context;
 use tokio::fs;
 
@@ -5,8 +7,9 @@ use crate::error::{AppError, AppResult};
 
 pub const DIR_PATH: &str = "/tmp/kubertui";
 pub const CACHE_PATH: &str = "/tmp/kubertui/cache.json";
-pub const ERROR_FILE_PATH: &str = "/tmp/kubertui/port_forward_error.log";
-pub const INFO_FILE_PATH: &str = "/tmp/kubertui/port_forward_info.log";
+pub const CACHE_LOCK_PATH: &str = "/tmp/kubertui/cache.lock";
+pub const PORT_FORWARD_LOGS_DIR: &str = "/tmp/kubertui/port-forwards";
+pub const EDIT_DIR: &str = "/tmp/kubertui/edit";
 
 pub async fn ensure_app_dir() -> AppResult<()> {
     fs::create_dir_all(DIR_PATH)
@@ -16,3 +19,27 @@ pub async fn ensure_app_dir() -> AppResult<()> {
 
     Ok(())
 }
+
+pub async fn ensure_port_forward_logs_dir() -> AppResult<()> {
+    fs::create_dir_all(PORT_FORWARD_LOGS_DIR)
+        .await
+        .with_context(|| format!("failed to create port forward logs dir: {PORT_FORWARD_LOGS_DIR}"))
+        .map_err(AppError::PortForwardError)?;
+
+    Ok(())
+}
+
+/// Only accessible to the user, the edited manifests may hold secrets.
+pub async fn ensure_edit_dir() -> AppResult<()> {
+    fs::create_dir_all(EDIT_DIR)
+        .await
+        .with_context(|| format!("failed to create edit dir: {EDIT_DIR}"))
+        .map_err(AppError::GeneralError)?;
+
+    fs::set_permissions(EDIT_DIR, Permissions::from_mode(0o700))
+        .await
+        .with_context(|| format!("failed to restrict edit dir: {EDIT_DIR}"))
+        .map_err(AppError::GeneralError)?;
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use crate::{
+    error::AppResult,
+    kubectl::{run_kubectl_text_command, with_context},
+};
+
+pub async fn get_contexts() -> AppResult<Vec<String>> {
+    let output =
+        run_kubectl_text_command("kubectl", vec!["config", "get-contexts", "-o", "name"]).await?;
+
+    Ok(output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+pub async fn get_current_context() -> AppResult<String> {
+    let output = run_kubectl_text_command("kubectl", vec!["config", "current-context"]).await?;
+
+    Ok(output.trim().to_string())
+}
+
+/// Namespace kubectl uses for `context` when none is passed, `default` unless the kubeconfig
+/// sets one.
+pub async fn get_context_namespace(context: &str) -> AppResult<String> {
+    let output = run_kubectl_text_command(
+        "kubectl",
+        with_context(
+            context,
+            vec!["config", "view", "--minify", "-o", "jsonpath={..namespace}"],
+        ),
+    )
+    .await?;
+
+    Ok(match output.trim() {
+        "" => "default".to_string(),
+        namespace => namespace.to_string(),
+    })
+}
@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::{
+    error::AppResult,
+    kubectl::{
+        KubectlStream, StreamEvent, run_kubectl_command, run_kubectl_text_command,
+        stream_kubectl_command, with_context,
+    },
+};
+
+#[derive(Debug, Clone)]
+pub struct Deployment {
+    pub name: String,
+    pub desired: u32,
+    pub ready: u32,
+    pub up_to_date: u32,
+    pub available: u32,
+    /// Images of the pod template's containers.
+    pub images: Vec<String>,
+    /// `matchLabels` of the selector. Pods matching its `matchExpressions` as well are a subset
+    /// of the pods matching these.
+    pub selector: BTreeMap<String, String>,
+}
+
+pub async fn get_deployments(context: &str, namespace: &str) -> AppResult<Vec<Deployment>> {
+    let parsed: ApiResponse = run_kubectl_command(
+        "kubectl",
+        with_context(
+            context,
+            vec!["get", "deployments", "-n", namespace, "-o", "json"],
+        ),
+    )
+    .await?;
+
+    Ok(parsed
+        .items
+        .into_iter()
+        .map(|item| Deployment {
+            name: item.metadata.name,
+            desired: item.spec.replicas.unwrap_or(1),
+            ready: item.status.ready_replicas,
+            up_to_date: item.status.updated_replicas,
+            available: item.status.available_replicas,
+            images: item
+                .spec
+                .template
+                .spec
+                .containers
+                .into_iter()
+                .map(|container| container.image)
+                .collect(),
+            selector: item.spec.selector.match_labels,
+        })
+        .collect())
+}
+
+/// Returns kubectl's summary, e.g. `deployment.apps/api scaled`.
+pub async fn scale_deployment(
+    context: &str,
+    namespace: &str,
+    name: &str,
+    replicas: u32,
+) -> AppResult<String> {
+    let target = format!("deployment/{name}");
+    let replicas = format!("--replicas={replicas}");
+
+    run_kubectl_text_command(
+        "kubectl",
+        with_context(
+            context,
+            vec!["scale", target.as_str(), replicas.as_str(), "-n", namespace],
+        ),
+    )
+    .await
+}
+
+/// Returns kubectl's summary, e.g. `deployment.apps/api restarted`.
+pub async fn restart_deployment(context: &str, namespace: &str, name: &str) -> AppResult<String> {
+    let target = format!("deployment/{name}");
+
+    run_kubectl_text_command(
+        "kubectl",
+        with_context(
+            context,
+            vec!["rollout", "restart", target.as_str(), "-n", namespace],
+        ),
+    )
+    .await
+}
+
+/// Follows `kubectl rollout status`, which prints a line per progress step and exits once the
+/// rollout is complete or has failed.
+pub fn stream_rollout_status<F>(
+    context: &str,
+    namespace: &str,
+    name: &str,
+    on_event: F,
+) -> AppResult<KubectlStream>
+where
+    F: Fn(StreamEvent) + Send + 'static,
+{
+    let target = format!("deployment/{name}");
+
+    stream_kubectl_command(
+        with_context(
+            context,
+            vec!["rollout", "status", target.as_str(), "-n", namespace],
+        ),
+        on_event,
+    )
+}
+
+#[derive(Deserialize)]
+struct ApiResponse {
+    items: Vec<Item>,
+}
+
+#[derive(Deserialize)]
+struct Item {
+    metadata: Metadata,
+    spec: Spec,
+    #[serde(default)]
+    status: Status,
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Spec {
+    replicas: Option<u32>,
+    selector: Selector,
+    template: Template,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Selector {
+    #[serde(default)]
+    match_labels: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct Template {
+    spec: TemplateSpec,
+}
+
+#[derive(Deserialize)]
+struct TemplateSpec {
+    containers: Vec<Container>,
+}
+
+#[derive(Deserialize)]
+struct Container {
+    #[serde(default)]
+    image: String,
+}
+
+/// Counts are left out of the status while they are zero.
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Status {
+    #[serde(default)]
+    ready_replicas: u32,
+    #[serde(default)]
+    updated_replicas: u32,
+    #[serde(default)]
+    available_replicas: u32,
+}
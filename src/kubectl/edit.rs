@@ -0,0 +1,53 @@
+use std::path::Path;
+
+use anyhow::Context;
+use tokio::process::Command;
+
+use crate::{
+    error::{AppError, AppResult},
+    kubectl::with_context,
+};
+
+/// What applying `path` would change on the live object, as a unified diff. Empty when the file
+/// matches the cluster.
+pub async fn diff_manifest(context: &str, path: &Path) -> AppResult<String> {
+    // kubectl diff exits with 1 when there are differences and above 1 when it failed.
+    run_with_manifest(context, "diff", path, &[0, 1]).await
+}
+
+/// Returns kubectl's summary, e.g. `pod/api configured`.
+pub async fn apply_manifest(context: &str, path: &Path) -> AppResult<String> {
+    run_with_manifest(context, "apply", path, &[0]).await
+}
+
+async fn run_with_manifest(
+    context: &str,
+    subcommand: &str,
+    path: &Path,
+    success_codes: &[i32],
+) -> AppResult<String> {
+    let path = path.to_string_lossy();
+    let args = with_context(context, vec![subcommand, "-f", path.as_ref()]);
+
+    let output = Command::new("kubectl")
+        .args(&args)
+        .output()
+        .await
+        .with_context(|| format!("Failed to run command kubectl '{}'", args.join(" ")))
+        .map_err(AppError::FailedRunKubeCtlCommand)?;
+
+    let is_success = output
+        .status
+        .code()
+        .is_some_and(|code| success_codes.contains(&code));
+
+    if !is_success {
+        // Validation errors and conflicts are worth reading in full, without the command around.
+        return Err(AppError::GeneralError(anyhow::anyhow!(
+            "{}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
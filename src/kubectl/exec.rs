@@ -0,0 +1,31 @@
+use tokio::process::Command;
+
+/// Prefers bash when the image has it and falls back to plain sh otherwise.
+const SHELL_DETECTION: &str = "command -v bash >/dev/null 2>&1 && exec bash || exec sh";
+
+pub fn exec_shell_command(
+    context: &str,
+    namespace: &str,
+    pod_name: &str,
+    container: &str,
+) -> Command {
+    let mut command = Command::new("kubectl");
+
+    command.args([
+        "--context",
+        context,
+        "exec",
+        "-it",
+        pod_name,
+        "-c",
+        container,
+        "-n",
+        namespace,
+        "--",
+        "sh",
+        "-c",
+        SHELL_DETECTION,
+    ]);
+
+    command
+}
@@ -0,0 +1,31 @@
+use crate::{
+    error::AppResult,
+    kubectl::{KubectlStream, StreamEvent, stream_kubectl_command, with_context},
+};
+
+const TAIL_LINES: &str = "1000";
+
+pub fn stream_logs<F>(
+    context: &str,
+    namespace: &str,
+    pod_name: &str,
+    container: &str,
+    previous: bool,
+    on_event: F,
+) -> AppResult<KubectlStream>
+where
+    F: Fn(StreamEvent) + Send + 'static,
+{
+    let mut args = vec![
+        "logs", pod_name, "-c", container, "-n", namespace, "--tail", TAIL_LINES,
+    ];
+
+    // Logs of a terminated container are complete, there is nothing to follow.
+    if previous {
+        args.push("--previous");
+    } else {
+        args.push("-f");
+    }
+
+    stream_kubectl_command(with_context(context, args), on_event)
+}
@@ -0,0 +1,63 @@
+use crate::{
+    error::AppResult,
+    kubectl::{run_kubectl_text_command, with_context},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestFormat {
+    Yaml,
+    Json,
+}
+
+impl ManifestFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ManifestFormat::Yaml => "yaml",
+            ManifestFormat::Json => "json",
+        }
+    }
+
+    pub fn toggle(&self) -> Self {
+        match self {
+            ManifestFormat::Yaml => ManifestFormat::Json,
+            ManifestFormat::Json => ManifestFormat::Yaml,
+        }
+    }
+}
+
+/// A single object of any kind, e.g. `pod` `api-7d4b9c-x2`.
+#[derive(Debug, Clone)]
+pub struct ResourceRef {
+    pub context: String,
+    pub namespace: String,
+    /// Resource type as kubectl accepts it, e.g. `pod` or `service`.
+    pub kind: String,
+    pub name: String,
+}
+
+impl ResourceRef {
+    /// `kind/name`, the way kubectl prints and accepts it.
+    pub fn target(&self) -> String {
+        format!("{}/{}", self.kind, self.name)
+    }
+}
+
+pub async fn get_manifest(resource: &ResourceRef, format: ManifestFormat) -> AppResult<String> {
+    let target = resource.target();
+
+    run_kubectl_text_command(
+        "kubectl",
+        with_context(
+            resource.context.as_str(),
+            vec![
+                "get",
+                target.as_str(),
+                "-n",
+                resource.namespace.as_str(),
+                "-o",
+                format.name(),
+            ],
+        ),
+    )
+    .await
+}
@@ -4,16 +4,50 @@ use tokio::process::Command;
 
 use crate::error::{AppError, AppResult};
 
+pub mod context;
+pub mod deployments;
+pub mod edit;
+pub mod exec;
+pub mod logs;
+pub mod manifest;
 pub mod namespace;
 pub mod pods;
 mod port_forward;
+mod port_forward_process;
+pub mod services;
+mod stream;
 
 pub use port_forward::*;
+pub use port_forward_process::*;
+pub use stream::*;
+
+/// Every call targets an explicit context so switching clusters inside the app never touches the
+/// `current-context` of the user's kubeconfig.
+fn with_context<'a>(context: &'a str, mut args: Vec<&'a str>) -> Vec<&'a str> {
+    args.splice(0..0, ["--context", context]);
+    args
+}
 
 async fn run_kubectl_command<T: for<'a> Deserialize<'a>>(
     command: &str,
     args: Vec<&str>,
 ) -> AppResult<T> {
+    let stdout = run_kubectl_text_command(command, args.clone()).await?;
+
+    let parsed: T = serde_json::from_str(&stdout)
+        .with_context(|| {
+            format!(
+                "invalid JSON from command: '{} {}'",
+                command,
+                args.join(" ")
+            )
+        })
+        .map_err(AppError::FailedRunKubeCtlCommand)?;
+
+    Ok(parsed)
+}
+
+async fn run_kubectl_text_command(command: &str, args: Vec<&str>) -> AppResult<String> {
     let output = Command::new(command)
         .args(&args)
         .output()
@@ -30,15 +64,5 @@ async fn run_kubectl_command<T: for<'a> Deserialize<'a>>(
         )));
     }
 
-    let parsed: T = serde_json::from_slice(&output.stdout)
-        .with_context(|| {
-            format!(
-                "invalid JSON from command: '{} {}'",
-                command,
-                args.join(" ")
-            )
-        })
-        .map_err(AppError::FailedRunKubeCtlCommand)?;
-
-    Ok(parsed)
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
 }
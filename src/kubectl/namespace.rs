@@ -1,6 +1,9 @@
 use serde::Deserialize;
 
-use crate::{error::AppError, kubectl::run_kubectl_command};
+use crate::{
+    error::AppError,
+    kubectl::{run_kubectl_command, with_context},
+};
 
 #[derive(Deserialize)]
 struct Response {
@@ -17,9 +20,12 @@ struct Metadata {
     name: String,
 }
 
-pub async fn get_namespaces() -> Result<Vec<String>, AppError> {
-    let parsed: Response =
-        run_kubectl_command("kubectl", vec!["get", "namespaces", "-o", "json"]).await?;
+pub async fn get_namespaces(context: &str) -> Result<Vec<String>, AppError> {
+    let parsed: Response = run_kubectl_command(
+        "kubectl",
+        with_context(context, vec!["get", "namespaces", "-o", "json"]),
+    )
+    .await?;
 
     Ok(parsed
         .items
@@ -1,10 +1,43 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
 use serde::{Deserialize, Serialize};
+use tokio::{sync::mpsc, time::sleep};
+
+use crate::{
+    error::{AppError, AppResult},
+    kubectl::{
+        KubectlStream, StreamEvent, run_kubectl_command, run_kubectl_text_command,
+        stream_kubectl_command, with_context,
+    },
+};
 
-use crate::{error::AppResult, kubectl::run_kubectl_command};
+const MIN_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+/// A watch that dies sooner than this is treated as failing rather than disconnected.
+const MIN_HEALTHY_WATCH: Duration = Duration::from_secs(10);
+const MAX_QUICK_FAILURES: usize = 3;
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// How long to poll before giving the watch another chance.
+const WATCH_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+/// Labels that differ between revisions of the same workload.
+const REVISION_LABELS: [&str; 2] = ["pod-template-hash", "controller-revision-hash"];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Pod {
     pub name: String,
+    #[serde(default)]
+    pub phase: String,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// Deployment owning the pod through its ReplicaSet, if any.
+    #[serde(default)]
+    pub deployment: Option<String>,
+    /// Deletion was requested, the pod stays listed until its containers have stopped.
+    #[serde(default)]
+    pub is_terminating: bool,
     pub container_statuses: Vec<PodStatus>,
     pub containers: Vec<PodContainer>,
 }
@@ -12,8 +45,49 @@ pub struct Pod {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PodContainer {
     pub name: String,
-    // TODO: There might be multiple ports.
-    pub port: u16,
+    /// Declared `containerPort`s, empty when the container does not declare any.
+    #[serde(default)]
+    pub ports: Vec<ContainerPort>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerPort {
+    pub name: Option<String>,
+    pub container_port: u16,
+    #[serde(default = "default_protocol")]
+    pub protocol: String,
+}
+
+impl ContainerPort {
+    /// Named ports read better than numbers, e.g. `grpc (9090/TCP)`.
+    pub fn label(&self) -> String {
+        match &self.name {
+            Some(name) => format!("{name} ({}/{})", self.container_port, self.protocol),
+            None => format!("{}/{}", self.container_port, self.protocol),
+        }
+    }
+}
+
+fn default_protocol() -> String {
+    "TCP".to_string()
+}
+
+impl Pod {
+    /// Labels identifying the pod's workload across rollouts and restarts.
+    pub fn workload_selector(&self) -> BTreeMap<String, String> {
+        self.labels
+            .iter()
+            .filter(|(key, _)| !REVISION_LABELS.contains(&key.as_str()))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
+
+    pub fn matches_selector(&self, selector: &BTreeMap<String, String>) -> bool {
+        selector
+            .iter()
+            .all(|(key, value)| self.labels.get(key) == Some(value))
+    }
 }
 
 impl AsRef<str> for PodContainer {
@@ -22,18 +96,365 @@ impl AsRef<str> for PodContainer {
     }
 }
 
-pub async fn get_pods_list(namespace: &str) -> AppResult<Vec<Pod>> {
+pub async fn get_pods_list(context: &str, namespace: &str) -> AppResult<Vec<Pod>> {
+    let (pods, _) = get_pods_snapshot(context, namespace).await?;
+    Ok(pods)
+}
+
+/// The pods along with the list's resource version, to watch for changes from exactly there.
+async fn get_pods_snapshot(context: &str, namespace: &str) -> AppResult<(Vec<Pod>, String)> {
     let parsed: ApiResponse = run_kubectl_command(
         "kubectl",
-        vec!["get", "pods", "-n", namespace, "-o", "json"],
+        with_context(context, vec!["get", "pods", "-n", namespace, "-o", "json"]),
     )
     .await?;
 
-    Ok(parsed
-        .items
-        .into_iter()
-        .map(|item| Pod {
+    let pods = parsed.items.into_iter().map(Pod::from).collect();
+    Ok((pods, parsed.metadata.resource_version))
+}
+
+/// Returns `pod_name` while it is still running, otherwise a running pod of the same workload.
+pub async fn resolve_pod(
+    context: &str,
+    namespace: &str,
+    pod_name: &str,
+    selector: &BTreeMap<String, String>,
+) -> AppResult<String> {
+    let pods = get_pods_list(context, namespace).await?;
+    let is_running = |pod: &&Pod| pod.phase == "Running";
+
+    if pods
+        .iter()
+        .filter(is_running)
+        .any(|pod| pod.name == pod_name)
+    {
+        return Ok(pod_name.to_string());
+    }
+
+    if selector.is_empty() {
+        return Err(AppError::PortForwardError(anyhow::anyhow!(
+            "pod {pod_name} is gone and its workload is unknown"
+        )));
+    }
+
+    pods.iter()
+        .filter(is_running)
+        .find(|pod| pod.matches_selector(selector))
+        .map(|pod| pod.name.clone())
+        .ok_or_else(|| {
+            AppError::PortForwardError(anyhow::anyhow!(
+                "no running pod found for the workload of {pod_name}"
+            ))
+        })
+}
+
+/// Requests the deletion of `names` without waiting for the pods to terminate, the watch
+/// reports them as terminating until they are gone. Returns kubectl's summary.
+pub async fn delete_pods(
+    context: &str,
+    namespace: &str,
+    names: &[String],
+    grace_period: Option<u32>,
+    force: bool,
+) -> AppResult<String> {
+    let grace_period = grace_period.map(|seconds| format!("--grace-period={seconds}"));
+
+    let mut args = vec!["delete", "pods", "-n", namespace, "--wait=false"];
+    args.extend(names.iter().map(String::as_str));
+    args.extend(grace_period.as_deref());
+
+    if force {
+        args.push("--force");
+    }
+
+    run_kubectl_text_command("kubectl", with_context(context, args)).await
+}
+
+/// Everything the describe view shows about a single pod.
+#[derive(Debug, Clone)]
+pub struct PodDetails {
+    pub name: String,
+    pub namespace: String,
+    pub created_at: Option<String>,
+    pub started_at: Option<String>,
+    pub phase: String,
+    pub labels: BTreeMap<String, String>,
+    pub annotations: BTreeMap<String, String>,
+    /// `Kind/name` of the owners, e.g. `ReplicaSet/api-7d4b9c`.
+    pub owners: Vec<String>,
+    pub node: Option<String>,
+    pub pod_ips: Vec<String>,
+    pub host_ip: Option<String>,
+    pub qos_class: Option<String>,
+    pub conditions: Vec<PodCondition>,
+    pub init_containers: Vec<ContainerDetails>,
+    pub containers: Vec<ContainerDetails>,
+    /// Volume names with their source, e.g. `configMap app-config`.
+    pub volumes: Vec<(String, String)>,
+    /// Oldest first. Events can be forbidden while the pod is not, so they fail on their own.
+    pub events: Result<Vec<PodEvent>, String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PodCondition {
+    pub kind: String,
+    pub status: String,
+    pub reason: Option<String>,
+    pub message: Option<String>,
+    pub last_transition_time: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContainerDetails {
+    pub name: String,
+    pub image: String,
+    pub ports: Vec<ContainerPort>,
+    /// Missing until the container has been scheduled.
+    pub state: Option<PodStatus>,
+    pub last_state: Option<PodStatus>,
+    pub ready: bool,
+    pub restart_count: u32,
+    pub requests: BTreeMap<String, String>,
+    pub limits: BTreeMap<String, String>,
+    /// Probe kinds with their description, e.g. `("liveness", "tcp-socket :8080 delay=0s ...")`.
+    pub probes: Vec<(&'static str, String)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PodEvent {
+    pub kind: String,
+    pub reason: String,
+    pub message: String,
+    pub count: u32,
+    pub last_seen: Option<String>,
+}
+
+const MAX_EVENTS: usize = 20;
+
+/// Fetches the pod and its events side by side.
+pub async fn get_pod_details(
+    context: &str,
+    namespace: &str,
+    pod_name: &str,
+) -> AppResult<PodDetails> {
+    let field_selector = format!("involvedObject.kind=Pod,involvedObject.name={pod_name}");
+
+    let (item, events) = tokio::join!(
+        run_kubectl_command::<Item>(
+            "kubectl",
+            with_context(
+                context,
+                vec!["get", "pod", pod_name, "-n", namespace, "-o", "json"],
+            ),
+        ),
+        run_kubectl_command::<EventsResponse>(
+            "kubectl",
+            with_context(
+                context,
+                vec![
+                    "get",
+                    "events",
+                    "-n",
+                    namespace,
+                    "--field-selector",
+                    field_selector.as_str(),
+                    "-o",
+                    "json",
+                ],
+            ),
+        ),
+    );
+
+    let mut details = PodDetails::from(item?);
+
+    details.events = events
+        .map(|events| {
+            let mut events: Vec<PodEvent> = events.items.into_iter().map(PodEvent::from).collect();
+
+            // RFC 3339 timestamps sort chronologically as strings.
+            events.sort_by(|a, b| a.last_seen.cmp(&b.last_seen));
+
+            let skip = events.len().saturating_sub(MAX_EVENTS);
+            events.split_off(skip)
+        })
+        .map_err(|err| err.to_string());
+
+    Ok(details)
+}
+
+#[derive(Debug, Clone)]
+pub enum PodsWatchEvent {
+    /// Full list of pods, sent every time the watch (re)connects and on every poll.
+    Snapshot(Vec<Pod>),
+    Applied(Pod),
+    Deleted(String),
+    Disconnected(String),
+    /// The watch keeps failing, from now on the list is refreshed by polling.
+    Polling,
+    /// Polling went on for `WATCH_RETRY_INTERVAL`, the watch is tried again.
+    Watching,
+}
+
+/// Keeps the pods of `namespace` up to date in a background task. The watch reconnects with
+/// backoff when kubectl dies and degrades to polling when it cannot be kept alive.
+pub fn watch_pods<F>(context: &str, namespace: &str, on_event: F) -> KubectlStream
+where
+    F: Fn(PodsWatchEvent) + Send + Sync + 'static,
+{
+    let context = context.to_string();
+    let namespace = namespace.to_string();
+
+    let task = tokio::spawn(async move {
+        let mut delay = MIN_RECONNECT_DELAY;
+        let mut quick_failures = 0;
+        let mut polling_since = None;
+
+        loop {
+            let resource_version =
+                match get_pods_snapshot(context.as_str(), namespace.as_str()).await {
+                    Ok((pods, resource_version)) => {
+                        on_event(PodsWatchEvent::Snapshot(pods));
+                        resource_version
+                    }
+                    Err(err) => {
+                        on_event(PodsWatchEvent::Disconnected(err.to_string()));
+                        sleep(delay).await;
+                        delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+                        continue;
+                    }
+                };
+
+            if let Some(since) = polling_since {
+                if Instant::now().duration_since(since) < WATCH_RETRY_INTERVAL {
+                    sleep(POLL_INTERVAL).await;
+                    continue;
+                }
+
+                // A single quick failure goes back to polling.
+                polling_since = None;
+                quick_failures = MAX_QUICK_FAILURES - 1;
+                on_event(PodsWatchEvent::Watching);
+            }
+
+            let started_at = Instant::now();
+            let reason = run_watch(
+                context.as_str(),
+                namespace.as_str(),
+                resource_version.as_str(),
+                &on_event,
+            )
+            .await;
+
+            if started_at.elapsed() < MIN_HEALTHY_WATCH {
+                quick_failures += 1;
+            } else {
+                quick_failures = 0;
+                delay = MIN_RECONNECT_DELAY;
+            }
+
+            if quick_failures >= MAX_QUICK_FAILURES {
+                polling_since = Some(Instant::now());
+                on_event(PodsWatchEvent::Polling);
+                continue;
+            }
+
+            on_event(PodsWatchEvent::Disconnected(reason));
+            sleep(delay).await;
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+    });
+
+    KubectlStream::from_task(task)
+}
+
+/// Runs a single watch session, starting right after `resource_version` so no change since the
+/// snapshot is missed, and returns why it ended. `kubectl get --watch` has no way to pass it,
+/// so the API is watched directly.
+async fn run_watch<F>(
+    context: &str,
+    namespace: &str,
+    resource_version: &str,
+    on_event: &F,
+) -> String
+where
+    F: Fn(PodsWatchEvent),
+{
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+
+    let path = format!(
+        "/api/v1/namespaces/{namespace}/pods?watch=true&resourceVersion={resource_version}"
+    );
+    let args = with_context(context, vec!["get", "--raw", path.as_str()]);
+
+    let _stream = match stream_kubectl_command(args, move |event| {
+        let _ = sender.send(event);
+    }) {
+        Ok(stream) => stream,
+        Err(err) => return err.to_string(),
+    };
+
+    let mut last_error = None;
+
+    // The API sends every event as a JSON document on a line of its own.
+    while let Some(event) = receiver.recv().await {
+        match event {
+            StreamEvent::Stdout(line) => {
+                let Ok(watch_event) = serde_json::from_str::<WatchEvent>(line.as_str()) else {
+                    continue;
+                };
+
+                // E.g. the resource version being too old, the watch ends right after.
+                if watch_event.kind == "ERROR" {
+                    last_error = watch_event.object["message"].as_str().map(String::from);
+                    continue;
+                }
+
+                if let Some(event) = watch_event.into_pods_event() {
+                    on_event(event);
+                }
+            }
+            StreamEvent::Stderr(line) => last_error = Some(line),
+            StreamEvent::Exited(code) => {
+                return last_error.unwrap_or_else(|| match code {
+                    Some(code) => format!("watch exited with code {code}"),
+                    None => "watch was terminated".to_string(),
+                });
+            }
+        }
+    }
+
+    "watch stopped".to_string()
+}
+
+#[derive(Deserialize)]
+struct WatchEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    object: serde_json::Value,
+}
+
+impl WatchEvent {
+    fn into_pods_event(self) -> Option<PodsWatchEvent> {
+        let item: Item = serde_json::from_value(self.object).ok()?;
+
+        match self.kind.as_str() {
+            "ADDED" | "MODIFIED" => Some(PodsWatchEvent::Applied(item.into())),
+            "DELETED" => Some(PodsWatchEvent::Deleted(item.metadata.name)),
+            _ => None,
+        }
+    }
+}
+
+impl From<Item> for Pod {
+    fn from(item: Item) -> Self {
+        let deployment = item.metadata.deployment_name();
+
+        Pod {
+            is_terminating: item.metadata.deletion_timestamp.is_some(),
             name: item.metadata.name,
+            phase: item.status.phase,
+            labels: item.metadata.labels,
+            deployment,
             container_statuses: item
                 .status
                 .container_statuses
@@ -46,20 +467,162 @@ pub async fn get_pods_list(namespace: &str) -> AppResult<Vec<Pod>> {
                 .into_iter()
                 .map(|item| PodContainer {
                     name: item.name,
-                    port: item
-                        .ports
-                        .first()
-                        .map(|port| port.container_port)
-                        .unwrap_or(0),
+                    ports: item.ports,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<Item> for PodDetails {
+    fn from(item: Item) -> Self {
+        let Item {
+            metadata,
+            status,
+            spec,
+        } = item;
+
+        let pod_ips = match (status.pod_ips, status.pod_ip) {
+            (pod_ips, _) if !pod_ips.is_empty() => pod_ips.into_iter().map(|ip| ip.ip).collect(),
+            (_, Some(pod_ip)) => vec![pod_ip],
+            _ => vec![],
+        };
+
+        PodDetails {
+            name: metadata.name,
+            namespace: metadata.namespace,
+            created_at: metadata.creation_timestamp,
+            started_at: status.start_time,
+            phase: status.phase,
+            labels: metadata.labels,
+            annotations: metadata.annotations,
+            owners: metadata
+                .owner_references
+                .into_iter()
+                .map(|owner| format!("{}/{}", owner.kind, owner.name))
+                .collect(),
+            node: spec.node_name,
+            pod_ips,
+            host_ip: status.host_ip,
+            qos_class: status.qos_class,
+            conditions: status
+                .conditions
+                .into_iter()
+                .map(|condition| PodCondition {
+                    kind: condition.kind,
+                    status: condition.status,
+                    reason: condition.reason,
+                    message: condition.message,
+                    last_transition_time: condition.last_transition_time,
                 })
                 .collect(),
+            init_containers: container_details(
+                spec.init_containers,
+                status.init_container_statuses,
+            ),
+            containers: container_details(spec.containers, status.container_statuses),
+            volumes: spec
+                .volumes
+                .iter()
+                .map(|volume| (volume.name.clone(), volume.describe()))
+                .collect(),
+            events: Ok(vec![]),
+        }
+    }
+}
+
+/// Pairs every container of the spec with its status, matched by name.
+fn container_details(
+    containers: Vec<Container>,
+    mut statuses: Vec<ContainerStatus>,
+) -> Vec<ContainerDetails> {
+    containers
+        .into_iter()
+        .map(|container| {
+            let status = statuses
+                .iter()
+                .position(|status| status.name == container.name)
+                .map(|index| statuses.swap_remove(index));
+
+            let probes = [
+                ("liveness", &container.liveness_probe),
+                ("readiness", &container.readiness_probe),
+                ("startup", &container.startup_probe),
+            ]
+            .into_iter()
+            .filter_map(|(kind, probe)| probe.as_ref().map(|probe| (kind, probe.describe())))
+            .collect();
+
+            let (state, last_state, ready, restart_count) = match status {
+                Some(status) => (
+                    Some(status.state),
+                    status.last_state,
+                    status.ready,
+                    status.restart_count,
+                ),
+                None => (None, None, false, 0),
+            };
+
+            ContainerDetails {
+                name: container.name,
+                image: container.image,
+                ports: container.ports,
+                state,
+                last_state,
+                ready,
+                restart_count,
+                requests: container.resources.requests,
+                limits: container.resources.limits,
+                probes,
+            }
         })
-        .collect())
+        .collect()
+}
+
+#[derive(Deserialize)]
+struct EventsResponse {
+    items: Vec<EventItem>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EventItem {
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    reason: String,
+    #[serde(default)]
+    message: String,
+    count: Option<u32>,
+    last_timestamp: Option<String>,
+    /// Set instead of the timestamps by the newer events API.
+    event_time: Option<String>,
+}
+
+impl From<EventItem> for PodEvent {
+    fn from(item: EventItem) -> Self {
+        PodEvent {
+            kind: item.kind,
+            reason: item.reason,
+            message: item.message,
+            count: item.count.unwrap_or(1),
+            last_seen: item.last_timestamp.or(item.event_time),
+        }
+    }
 }
 
 #[derive(Deserialize)]
 struct ApiResponse {
     items: Vec<Item>,
+    #[serde(default)]
+    metadata: ListMetadata,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListMetadata {
+    #[serde(default)]
+    resource_version: String,
 }
 
 #[derive(Deserialize)]
@@ -70,38 +633,252 @@ struct Item {
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct Spec {
     containers: Vec<Container>,
+    #[serde(default)]
+    init_containers: Vec<Container>,
+    node_name: Option<String>,
+    #[serde(default)]
+    volumes: Vec<Volume>,
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct Container {
     name: String,
+    #[serde(default)]
+    image: String,
+    #[serde(default)]
     ports: Vec<ContainerPort>,
+    #[serde(default)]
+    resources: Resources,
+    liveness_probe: Option<Probe>,
+    readiness_probe: Option<Probe>,
+    startup_probe: Option<Probe>,
+}
+
+#[derive(Default, Deserialize)]
+struct Resources {
+    #[serde(default)]
+    requests: BTreeMap<String, String>,
+    #[serde(default)]
+    limits: BTreeMap<String, String>,
 }
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct ContainerPort {
-    container_port: u16,
-    // name: String,
-    // protocol: String,
+struct Probe {
+    http_get: Option<HttpGetAction>,
+    tcp_socket: Option<TcpSocketAction>,
+    exec: Option<ExecAction>,
+    grpc: Option<GrpcAction>,
+    #[serde(default)]
+    initial_delay_seconds: u32,
+    #[serde(default = "default_probe_timeout")]
+    timeout_seconds: u32,
+    #[serde(default = "default_probe_period")]
+    period_seconds: u32,
+    #[serde(default = "default_probe_threshold")]
+    success_threshold: u32,
+    #[serde(default = "default_probe_failure_threshold")]
+    failure_threshold: u32,
+}
+
+fn default_probe_timeout() -> u32 {
+    1
+}
+
+fn default_probe_period() -> u32 {
+    10
+}
+
+fn default_probe_threshold() -> u32 {
+    1
+}
+
+fn default_probe_failure_threshold() -> u32 {
+    3
+}
+
+#[derive(Deserialize)]
+struct HttpGetAction {
+    path: Option<String>,
+    /// A number or the name of a container port.
+    port: serde_json::Value,
+    scheme: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TcpSocketAction {
+    port: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct ExecAction {
+    #[serde(default)]
+    command: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct GrpcAction {
+    port: u16,
+}
+
+impl Probe {
+    /// Same shape as `kubectl describe`, e.g.
+    /// `http-get http://:8080/healthz delay=0s timeout=1s period=10s #success=1 #failure=3`.
+    fn describe(&self) -> String {
+        let action = if let Some(http_get) = &self.http_get {
+            format!(
+                "http-get {}://:{}{}",
+                http_get.scheme.as_deref().unwrap_or("HTTP").to_lowercase(),
+                json_scalar(&http_get.port),
+                http_get.path.as_deref().unwrap_or("/")
+            )
+        } else if let Some(tcp_socket) = &self.tcp_socket {
+            format!("tcp-socket :{}", json_scalar(&tcp_socket.port))
+        } else if let Some(exec) = &self.exec {
+            format!("exec [{}]", exec.command.join(" "))
+        } else if let Some(grpc) = &self.grpc {
+            format!("grpc :{}", grpc.port)
+        } else {
+            "unknown".to_string()
+        };
+
+        format!(
+            "{action} delay={}s timeout={}s period={}s #success={} #failure={}",
+            self.initial_delay_seconds,
+            self.timeout_seconds,
+            self.period_seconds,
+            self.success_threshold,
+            self.failure_threshold
+        )
+    }
 }
 
 #[derive(Deserialize)]
+struct Volume {
+    name: String,
+    /// The only other key is the volume's source, e.g. `configMap` or `emptyDir`.
+    #[serde(flatten)]
+    source: BTreeMap<String, serde_json::Value>,
+}
+
+impl Volume {
+    /// Source kind followed by what it refers to, e.g. `configMap app-config`.
+    fn describe(&self) -> String {
+        let Some((kind, source)) = self.source.iter().next() else {
+            return "unknown".to_string();
+        };
+
+        let reference = match kind.as_str() {
+            "configMap" => source.get("name"),
+            "secret" => source.get("secretName"),
+            "persistentVolumeClaim" => source.get("claimName"),
+            "hostPath" => source.get("path"),
+            "nfs" => source.get("path"),
+            _ => None,
+        };
+
+        match reference {
+            Some(reference) => format!("{kind} {}", json_scalar(reference)),
+            None => kind.clone(),
+        }
+    }
+}
+
+/// Strings without their quotes, anything else as JSON.
+fn json_scalar(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct Metadata {
     name: String,
+    #[serde(default)]
+    namespace: String,
+    creation_timestamp: Option<String>,
+    deletion_timestamp: Option<String>,
+    #[serde(default)]
+    labels: BTreeMap<String, String>,
+    #[serde(default)]
+    annotations: BTreeMap<String, String>,
+    #[serde(default)]
+    owner_references: Vec<OwnerReference>,
+}
+
+#[derive(Deserialize)]
+struct OwnerReference {
+    kind: String,
+    name: String,
+}
+
+impl Metadata {
+    /// ReplicaSets created by a deployment are named `<deployment>-<pod-template-hash>`.
+    fn deployment_name(&self) -> Option<String> {
+        let hash = self.labels.get("pod-template-hash")?;
+
+        self.owner_references
+            .iter()
+            .find(|owner| owner.kind == "ReplicaSet")
+            .and_then(|owner| owner.name.strip_suffix(format!("-{hash}").as_str()))
+            .map(String::from)
+    }
 }
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct Status {
+    #[serde(default)]
+    phase: String,
+    #[serde(rename = "podIPs", default)]
+    pod_ips: Vec<PodIp>,
+    #[serde(rename = "podIP")]
+    pod_ip: Option<String>,
+    #[serde(rename = "hostIP")]
+    host_ip: Option<String>,
+    qos_class: Option<String>,
+    start_time: Option<String>,
+    #[serde(default)]
+    conditions: Vec<Condition>,
+    #[serde(default)]
     container_statuses: Vec<ContainerStatus>,
+    #[serde(default)]
+    init_container_statuses: Vec<ContainerStatus>,
+}
+
+#[derive(Deserialize)]
+struct PodIp {
+    ip: String,
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Condition {
+    #[serde(rename = "type")]
+    kind: String,
+    status: String,
+    reason: Option<String>,
+    message: Option<String>,
+    last_transition_time: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct ContainerStatus {
+    #[serde(default)]
+    name: String,
     state: PodStatus,
+    last_state: Option<PodStatus>,
+    #[serde(default)]
+    ready: bool,
+    #[serde(default)]
+    restart_count: u32,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -116,6 +893,7 @@ pub enum PodStatus {
 pub enum KnownPodStatus {
     #[serde(rename_all = "camelCase")]
     Terminated {
+        #[serde(rename = "containerID")]
         container_id: String,
         exit_code: usize,
         finished_at: String,
@@ -131,3 +909,48 @@ pub enum KnownPodStatus {
     #[serde(rename_all = "camelCase")]
     Running { started_at: String },
 }
+
+impl PodStatus {
+    /// One line summary, e.g. `Waiting: CrashLoopBackOff` or `Terminated: Error (exit code 1)`.
+    pub fn describe(&self) -> String {
+        match self {
+            PodStatus::Known(KnownPodStatus::Running { started_at }) => {
+                format!("Running since {started_at}")
+            }
+            PodStatus::Known(KnownPodStatus::Waiting { reason, message }) => match message {
+                Some(message) => format!("Waiting: {reason} ({message})"),
+                None => format!("Waiting: {reason}"),
+            },
+            PodStatus::Known(KnownPodStatus::Terminated {
+                reason,
+                exit_code,
+                finished_at,
+                ..
+            }) => format!("Terminated: {reason} (exit code {exit_code}) at {finished_at}"),
+            // Partial states, e.g. a terminated container that never got an id.
+            PodStatus::Unknown(value) => {
+                let Some((state, details)) =
+                    value.as_object().and_then(|state| state.iter().next())
+                else {
+                    return "None".to_string();
+                };
+
+                let mut chars = state.chars();
+                let mut description: String = chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default();
+
+                if let Some(reason) = details.get("reason").and_then(|reason| reason.as_str()) {
+                    description.push_str(format!(": {reason}").as_str());
+                }
+
+                if let Some(exit_code) = details.get("exitCode") {
+                    description.push_str(format!(" (exit code {exit_code})").as_str());
+                }
+
+                description
+            }
+        }
+    }
+}
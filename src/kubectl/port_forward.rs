@@ -1,107 +1,458 @@
 use std::{
-    fs::File,
-    io::{BufRead, BufReader, Read},
+    io,
+    net::{Ipv4Addr, Ipv6Addr, TcpListener},
     os::unix::process::CommandExt,
+    path::{Path, PathBuf},
     process::{Command, Stdio},
     time::{Duration, Instant},
 };
 
 use anyhow::Context;
-use tokio::time::sleep;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::{
+    io::AsyncBufReadExt,
+    time::{sleep, timeout},
+};
 
 use crate::{
     error::{AppError, AppResult},
-    files::{ERROR_FILE_PATH, INFO_FILE_PATH, ensure_app_dir},
+    files::{PORT_FORWARD_LOGS_DIR, ensure_port_forward_logs_dir},
+    kubectl::{KubectlStream, StreamEvent, stream_command},
 };
 
-const TIME_OUT_SECONDS: u64 = 3;
+const DEFAULT_READY_TIMEOUT: Duration = Duration::from_secs(10);
+/// Overrides `DEFAULT_READY_TIMEOUT`, in seconds.
+pub const READY_TIMEOUT_ENV: &str = "KUBERTUI_PORT_FORWARD_TIMEOUT";
+const MONITOR_INTERVAL: Duration = Duration::from_secs(1);
+const LOG_TAIL_LINES: &str = "1000";
+/// Size at which a forward's log is moved aside to `<log>.1`, replacing the previous one.
+const MAX_LOG_SIZE: u64 = 1024 * 1024;
+/// How long a stopped kubectl gets to exit and release its local ports.
+pub const PORT_RELEASE_TIMEOUT: Duration = Duration::from_secs(3);
+const PORT_RELEASE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Why kubectl could not establish a forward.
+#[derive(Error, Debug)]
+pub enum PortForwardFailure {
+    #[error("local port {0} is already in use")]
+    PortInUse(u16),
+
+    #[error("{0}")]
+    TargetNotFound(String),
+
+    #[error("{0}")]
+    Forbidden(String),
+
+    #[error("kubectl did not report the forward as ready within {}s", .0.as_secs())]
+    Timeout(Duration),
+
+    #[error("kubectl exited: {0}")]
+    Exited(String),
+
+    #[error("{0}")]
+    Kubectl(String),
+}
+
+impl PortForwardFailure {
+    /// Classifies the error kubectl printed before giving up.
+    fn from_error(line: &str, ports: &[PortMapping]) -> Self {
+        // `Error from server (NotFound): pods "api" not found`
+        let message = line
+            .split_once("): ")
+            .map(|(_, message)| message)
+            .unwrap_or(line.trim_start_matches("error: "))
+            .to_string();
+
+        if line.contains("unable to listen on") {
+            // `error: unable to listen on any of the requested ports: [{8080 80}]`
+            let port = ports
+                .iter()
+                .map(|ports| ports.local_port)
+                .find(|port| line.contains(format!("{{{port} ").as_str()))
+                .or(ports.first().map(|ports| ports.local_port))
+                .unwrap_or_default();
+
+            PortForwardFailure::PortInUse(port)
+        } else if line.contains("(NotFound)") || line.contains("not found") {
+            PortForwardFailure::TargetNotFound(message)
+        } else if line.contains("(Forbidden)") || line.contains("forbidden") {
+            PortForwardFailure::Forbidden(message)
+        } else {
+            PortForwardFailure::Kubectl(message)
+        }
+    }
+}
+
+/// How long kubectl gets to report a forward as ready, see `READY_TIMEOUT_ENV`.
+pub fn ready_timeout() -> Duration {
+    std::env::var(READY_TIMEOUT_ENV)
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_READY_TIMEOUT)
+}
+
+/// Kind of resource a forward points at. Services and deployments survive pod churn because
+/// kubectl picks a ready pod every time the forward starts.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TargetKind {
+    #[default]
+    Pod,
+    Service,
+    Deployment,
+}
+
+impl TargetKind {
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            TargetKind::Pod => "pod",
+            TargetKind::Service => "svc",
+            TargetKind::Deployment => "deploy",
+        }
+    }
+
+    /// Formats `name` as a kubectl resource argument, e.g. `svc/api`.
+    pub fn target(&self, name: &str) -> String {
+        format!("{}/{}", self.prefix(), name)
+    }
+}
+
+/// One `local:remote` pair of a forward. A single kubectl process can serve several of them.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortMapping {
+    pub local_port: u16,
+    pub app_port: u16,
+}
+
+impl PortMapping {
+    pub fn new(local_port: u16, app_port: u16) -> Self {
+        Self {
+            local_port,
+            app_port,
+        }
+    }
+
+    /// Formatted as kubectl expects it, e.g. `8080:80`.
+    pub fn arg(&self) -> String {
+        format!("{}:{}", self.local_port, self.app_port)
+    }
+}
+
+/// Log file of one forward, named after its context, namespace and ports. Not after its pod,
+/// which changes when the forward is moved to another pod of the workload, the log goes on.
+/// Both stdout and stderr go there, so connection errors show up next to the
+/// "Handling connection for" lines that preceded them.
+pub fn port_forward_log_path(context: &str, namespace: &str, ports: &[PortMapping]) -> PathBuf {
+    let ports = ports
+        .iter()
+        .map(|ports| format!("{}-{}", ports.local_port, ports.app_port))
+        .collect::<Vec<_>>()
+        .join("_");
+
+    let name = format!("{context}_{namespace}_{ports}").replace(
+        |ch: char| !ch.is_ascii_alphanumeric() && !"-_.".contains(ch),
+        "_",
+    );
+
+    Path::new(PORT_FORWARD_LOGS_DIR).join(format!("{name}.log"))
+}
+
+/// kubectl binds forwards to both loopback addresses, so the port has to be free on each. Only a
+/// port in use counts as taken, e.g. a host without IPv6 can not bind `::1` at all.
+pub fn is_local_port_free(port: u16) -> bool {
+    let is_in_use = |result: io::Result<TcpListener>| matches!(result, Err(err) if err.kind() == io::ErrorKind::AddrInUse);
+
+    !is_in_use(TcpListener::bind((Ipv4Addr::LOCALHOST, port)))
+        && !is_in_use(TcpListener::bind((Ipv6Addr::LOCALHOST, port)))
+}
+
+/// Waits until the local ports of `ports` are free, giving up after `PORT_RELEASE_TIMEOUT`.
+/// Returns whether they were released.
+pub async fn wait_for_ports_release(ports: &[PortMapping]) -> bool {
+    let stopped_at = Instant::now();
+
+    loop {
+        if ports
+            .iter()
+            .all(|ports| is_local_port_free(ports.local_port))
+        {
+            return true;
+        }
 
+        if stopped_at.elapsed() >= PORT_RELEASE_TIMEOUT {
+            return false;
+        }
+
+        sleep(PORT_RELEASE_INTERVAL).await;
+    }
+}
+
+/// Next free port after `port`, skipping the ones `is_taken` reports as reserved elsewhere.
+pub fn find_free_local_port(port: u16, is_taken: impl Fn(u16) -> bool) -> Option<u16> {
+    (port.saturating_add(1)..=u16::MAX)
+        .chain(1024..port)
+        .find(|&candidate| !is_taken(candidate) && is_local_port_free(candidate))
+}
+
+/// Starts kubectl in its own session, so the forward outlives the app, and waits until it
+/// reports the forward as ready or fails.
+///
+/// kubectl's output goes through a detached `tee` that appends it to `log_path`. The app reads
+/// tee's copy to detect readiness and lets go of it afterwards, tee keeps logging on its own.
+/// kubectl itself can not write to the app directly: it would be killed by `SIGPIPE` once the
+/// app exits.
 pub async fn start_port_forward(
+    context: &str,
     namespace: &str,
-    pod_name: &str,
-    local_port: u16,
-    app_port: u16,
+    target: &str,
+    ports: &[PortMapping],
+    log_path: &Path,
+    ready_timeout: Duration,
 ) -> AppResult<u32> {
-    ensure_app_dir().await?;
+    ensure_port_forward_logs_dir().await?;
+    rotate_port_forward_log(log_path);
 
-    let info_log_file =
-        File::create(INFO_FILE_PATH).context("Failed to create a port_forward_log file")?;
+    // Fail right away instead of waiting for kubectl to time out on a busy port.
+    if let Some(busy) = ports
+        .iter()
+        .find(|ports| !is_local_port_free(ports.local_port))
+    {
+        return Err(PortForwardFailure::PortInUse(busy.local_port).into());
+    }
+
+    let (output_reader, output_writer) = std::io::pipe()
+        .context("Failed to create a pipe for kubectl's output")
+        .map_err(AppError::PortForwardError)?;
+
+    let stderr_writer = output_writer
+        .try_clone()
+        .context("Failed to create a pipe for kubectl's output")
+        .map_err(AppError::PortForwardError)?;
+
+    let mut tee = unsafe {
+        tokio::process::Command::new("tee")
+            .arg("-a")
+            .arg(log_path)
+            .stdin(output_reader)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .pre_exec(start_tee_session)
+            .spawn()
+            .context("Failed to start tee for the port forward log")
+            .map_err(AppError::PortForwardError)?
+    };
 
-    let error_log_file =
-        File::create(ERROR_FILE_PATH).context("Failed to create a port_forward_error file")?;
+    let port_args: Vec<String> = ports.iter().map(PortMapping::arg).collect();
 
-    let pid = unsafe {
+    let child = unsafe {
         Command::new("kubectl")
-            .args([
-                "port-forward",
-                pod_name,
-                format!("{}:{}", local_port, app_port).as_str(),
-                "-n",
-                namespace,
-            ])
+            .args(["--context", context, "port-forward", target])
+            .args(&port_args)
+            .args(["-n", namespace])
             .stdin(Stdio::null())
-            .stdout(info_log_file)
-            .stderr(error_log_file)
-            .pre_exec(|| {
-                // Try to create a new session
-                // This call is unsafe because it runs in the child process after fork but before exec.
-                let pid = libc::setsid();
-                if pid == -1 {
-                    return Err(std::io::Error::last_os_error());
-                }
-                Ok(())
-            })
+            .stdout(output_writer)
+            .stderr(stderr_writer)
+            .pre_exec(start_session)
             .spawn()
             .context("Failed to start port-forward process")
             .map_err(AppError::PortForwardError)?
-            .id()
     };
 
-    let mut buf_reader = BufReader::new(
-        File::open(INFO_FILE_PATH)
-            .context("failed to open info file path")
-            .map_err(AppError::PortForwardError)?,
-    );
+    let guard = StartGuard(Some(child));
 
-    let mut line = String::new();
+    let Some(output) = tee.stdout.take() else {
+        return Err(AppError::PortForwardError(anyhow::anyhow!(
+            "Failed to read kubectl's output"
+        )));
+    };
 
-    let now = Instant::now();
-    let timeout = Duration::from_secs(TIME_OUT_SECONDS);
+    timeout(ready_timeout, wait_until_ready(output, ports))
+        .await
+        .unwrap_or(Err(PortForwardFailure::Timeout(ready_timeout)))?;
 
-    loop {
-        if now.elapsed() > timeout {
-            let mut logged_error = String::new();
-
-            File::open(ERROR_FILE_PATH)
-                .context("failed to open error file path")
-                .map_err(AppError::PortForwardError)?
-                .read_to_string(&mut logged_error)
-                .context("failed to read error from log file")
-                .map_err(AppError::PortForwardError)?;
-
-            let error_message = if !logged_error.is_empty() {
-                logged_error
-            } else {
-                format!("Port Forward timed out after {TIME_OUT_SECONDS}")
-            };
-
-            return Err(AppError::PortForwardError(anyhow::anyhow!(error_message)));
-        }
+    Ok(guard.release())
+}
+
+/// Stops kubectl unless it got ready, also when the start is dropped halfway, e.g. as its job
+/// was replaced. Nothing would know of the process otherwise, it outlives the app.
+struct StartGuard(Option<std::process::Child>);
 
-        line.clear();
+impl StartGuard {
+    /// Hands the running forward over to the caller.
+    fn release(mut self) -> u32 {
+        self.0.take().map(|child| child.id()).unwrap_or_default()
+    }
+}
 
-        buf_reader
-            .read_line(&mut line)
-            .context("failed to read from port forward log file")?;
+impl Drop for StartGuard {
+    fn drop(&mut self) {
+        let Some(mut child) = self.0.take() else {
+            return;
+        };
 
+        let _ = stop_port_forward(child.id());
+
+        // Reap it, no monitor is going to.
+        std::thread::spawn(move || child.wait());
+    }
+}
+
+/// Runs in the child before exec. `setsid` detaches it from the app's terminal, so closing
+/// the terminal does not take the forward down.
+fn start_session() -> std::io::Result<()> {
+    if unsafe { libc::setsid() } == -1 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Like `start_session`, and keeps tee writing the log once the app stopped reading its output.
+/// With `SIGPIPE` ignored, tee gets `EPIPE` instead of being killed and goes on with its other
+/// outputs, which every tee does, unlike the GNU only `-p`.
+fn start_tee_session() -> std::io::Result<()> {
+    if unsafe { libc::signal(libc::SIGPIPE, libc::SIG_IGN) } == libc::SIG_ERR {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    start_session()
+}
+
+async fn wait_until_ready(
+    output: tokio::process::ChildStdout,
+    ports: &[PortMapping],
+) -> Result<(), PortForwardFailure> {
+    let mut lines = tokio::io::BufReader::new(output).lines();
+    let mut logged = String::new();
+
+    while let Ok(Some(line)) = lines.next_line().await {
         if line.contains("Forwarding from") {
-            break;
+            return Ok(());
+        }
+
+        if line.starts_with("error:") || line.starts_with("Error from server") {
+            return Err(PortForwardFailure::from_error(line.as_str(), ports));
+        }
+
+        logged.push_str(line.as_str());
+        logged.push('\n');
+    }
+
+    // tee only stops once kubectl closed its output, i.e. exited.
+    Err(PortForwardFailure::Exited(logged.trim_end().to_string()))
+}
+
+/// Follows a forward's log file, including the lines written before it was opened.
+pub fn stream_port_forward_log<F>(log_path: &Path, on_event: F) -> AppResult<KubectlStream>
+where
+    F: Fn(StreamEvent) + Send + 'static,
+{
+    let log_path = log_path.to_string_lossy();
+
+    // `-F` keeps following when the forward is restarted, its log is rotated or the file does
+    // not exist yet.
+    stream_command(
+        "tail",
+        vec!["-n", LOG_TAIL_LINES, "-F", log_path.as_ref()],
+        on_event,
+    )
+}
+
+/// Port forwards run in their own session (see `setsid` above), so the whole process group is
+/// terminated at once. Adopted forwards started outside the app usually are not group leaders,
+/// those are signalled directly.
+pub fn stop_port_forward(pid: u32) -> AppResult<()> {
+    let mut result = unsafe { libc::killpg(pid as libc::pid_t, libc::SIGTERM) };
+
+    if result == -1 && std::io::Error::last_os_error().raw_os_error() == Some(libc::ESRCH) {
+        result = unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM) };
+    }
+
+    if result == -1 {
+        let err = std::io::Error::last_os_error();
+
+        // The process is already gone, which is what we wanted.
+        if err.raw_os_error() == Some(libc::ESRCH) {
+            return Ok(());
         }
 
-        sleep(Duration::from_millis(100)).await;
+        return Err(AppError::PortForwardError(
+            anyhow::Error::new(err).context(format!("failed to stop port forward (pid {pid})")),
+        ));
     }
 
-    Ok(pid)
+    Ok(())
+}
+
+/// Checks whether the forward's process is still running. Forwards started by this process are
+/// reaped here, otherwise they would linger as zombies and look alive forever.
+pub fn is_process_alive(pid: u32) -> bool {
+    let pid = pid as libc::pid_t;
+    let mut status = 0;
+
+    match unsafe { libc::waitpid(pid, &mut status, libc::WNOHANG) } {
+        0 => true,
+        reaped if reaped == pid => false,
+        // Not our child (e.g. started by a previous run of the app), fall back to a signal probe.
+        _ => {
+            let is_signalable = unsafe { libc::kill(pid, 0) } == 0;
+            is_signalable || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+        }
+    }
+}
+
+/// Waits for a forward stopped on purpose to exit, without reporting it. A forward started by
+/// the app would linger as a zombie otherwise, as no monitor reaps it anymore.
+pub fn reap_port_forward(pid: u32) {
+    // Once the runtime is shut down, the app is exiting and takes its zombies along.
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+        return;
+    };
+
+    runtime.spawn(async move {
+        while is_process_alive(pid) {
+            sleep(MONITOR_INTERVAL).await;
+        }
+    });
+}
+
+/// Copies a log grown past `MAX_LOG_SIZE` to `<log>.1` and empties it. tee appends, so it goes
+/// on writing at the start of the emptied file, and `tail -F` notices the truncation. Failures
+/// only mean the log grows a bit longer.
+pub fn rotate_port_forward_log(log_path: &Path) {
+    let is_too_big = std::fs::metadata(log_path).is_ok_and(|meta| meta.len() > MAX_LOG_SIZE);
+
+    if !is_too_big {
+        return;
+    }
+
+    let mut rotated = log_path.as_os_str().to_owned();
+    rotated.push(".1");
+
+    if std::fs::copy(log_path, &rotated).is_ok()
+        && let Ok(file) = std::fs::OpenOptions::new().write(true).open(log_path)
+    {
+        let _ = file.set_len(0);
+    }
+}
+
+/// Calls `on_exit` once the forward's process is gone, keeping its log in check meanwhile.
+/// Dropping the handle stops monitoring.
+pub fn monitor_port_forward<F>(pid: u32, log_path: PathBuf, on_exit: F) -> KubectlStream
+where
+    F: FnOnce() + Send + 'static,
+{
+    let task = tokio::spawn(async move {
+        while is_process_alive(pid) {
+            rotate_port_forward_log(&log_path);
+            sleep(MONITOR_INTERVAL).await;
+        }
+
+        on_exit();
+    });
+
+    KubectlStream::from_task(task)
 }
 
 // let output = Command::new("kubectl")
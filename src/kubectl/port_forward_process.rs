@@ -0,0 +1,236 @@
+use std::{fs, path::Path};
+
+use crate::kubectl::{PortMapping, TargetKind};
+
+/// Flags of `kubectl port-forward` and the global ones that take a separate value, so the value
+/// is not mistaken for the target or a port.
+const FLAGS_WITH_VALUE: [&str; 12] = [
+    "--context",
+    "-n",
+    "--namespace",
+    "--address",
+    "--pod-running-timeout",
+    "--kubeconfig",
+    "--cluster",
+    "--user",
+    "-s",
+    "--server",
+    "--token",
+    "--as",
+];
+
+/// A running `kubectl port-forward` as read from its command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortForwardProcess {
+    pub pid: u32,
+    /// `None` when kubectl was started without `--context` and uses the kubeconfig's current one.
+    pub context: Option<String>,
+    /// `None` when kubectl was started without `-n` and uses the namespace of its context.
+    pub namespace: Option<String>,
+    pub kind: TargetKind,
+    pub target_name: String,
+    pub ports: Vec<PortMapping>,
+}
+
+/// Reads the process's command line and returns it when it is a `kubectl port-forward` this app
+/// can manage. Anything else, e.g. an unrelated process that got the PID after a reboot, is `None`.
+pub fn inspect_port_forward(pid: u32) -> Option<PortForwardProcess> {
+    let cmdline = fs::read(format!("/proc/{pid}/cmdline")).ok()?;
+
+    let args: Vec<String> = cmdline
+        .split(|byte| *byte == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| String::from_utf8_lossy(arg).into_owned())
+        .collect();
+
+    parse_port_forward_args(pid, &args)
+}
+
+/// Every `kubectl port-forward` process visible to the user, including the ones started in a
+/// terminal outside the app.
+pub fn find_port_forward_processes() -> Vec<PortForwardProcess> {
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return vec![];
+    };
+
+    entries
+        .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse::<u32>().ok())
+        .filter_map(inspect_port_forward)
+        .collect()
+}
+
+fn parse_port_forward_args(pid: u32, args: &[String]) -> Option<PortForwardProcess> {
+    let (program, args) = args.split_first()?;
+
+    if Path::new(program).file_name()? != "kubectl" {
+        return None;
+    }
+
+    let mut context = None;
+    let mut namespace = None;
+    let mut positional = vec![];
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        if let Some((flag, value)) = arg.split_once('=')
+            && flag.starts_with('-')
+        {
+            match flag {
+                "--context" => context = Some(value.to_string()),
+                "-n" | "--namespace" => namespace = Some(value.to_string()),
+                _ => {}
+            }
+
+            continue;
+        }
+
+        if FLAGS_WITH_VALUE.contains(&arg.as_str()) {
+            let value = args.next()?.clone();
+
+            match arg.as_str() {
+                "--context" => context = Some(value),
+                "-n" | "--namespace" => namespace = Some(value),
+                _ => {}
+            }
+
+            continue;
+        }
+
+        if !arg.starts_with('-') {
+            positional.push(arg.as_str());
+        }
+    }
+
+    let [command, target, ports @ ..] = positional.as_slice() else {
+        return None;
+    };
+
+    if *command != "port-forward" {
+        return None;
+    }
+
+    let (kind, target_name) = parse_target(target)?;
+
+    // `:80` lets kubectl pick a random local port, there is nothing to match it against.
+    let ports = ports
+        .iter()
+        .map(|ports| match ports.split_once(':') {
+            Some((local, app)) => Some(PortMapping::new(local.parse().ok()?, app.parse().ok()?)),
+            None => {
+                let port = ports.parse().ok()?;
+                Some(PortMapping::new(port, port))
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    if ports.is_empty() {
+        return None;
+    }
+
+    Some(PortForwardProcess {
+        pid,
+        context,
+        namespace,
+        kind,
+        target_name,
+        ports,
+    })
+}
+
+fn parse_target(target: &str) -> Option<(TargetKind, String)> {
+    let Some((kind, name)) = target.split_once('/') else {
+        return Some((TargetKind::Pod, target.to_string()));
+    };
+
+    // Accept fully qualified kinds such as `deployment.apps`.
+    let kind = match kind.split('.').next()? {
+        "pod" | "pods" | "po" => TargetKind::Pod,
+        "svc" | "service" | "services" => TargetKind::Service,
+        "deploy" | "deployment" | "deployments" => TargetKind::Deployment,
+        _ => return None,
+    };
+
+    Some((kind, name.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(cmdline: &str) -> Option<PortForwardProcess> {
+        let args: Vec<String> = cmdline.split_whitespace().map(String::from).collect();
+        parse_port_forward_args(42, &args)
+    }
+
+    #[test]
+    fn parses_flags_with_separate_values() {
+        let process = parse("kubectl --context prod port-forward svc/api 8080:80 -n shop").unwrap();
+
+        assert_eq!(
+            process,
+            PortForwardProcess {
+                pid: 42,
+                context: Some("prod".to_string()),
+                namespace: Some("shop".to_string()),
+                kind: TargetKind::Service,
+                target_name: "api".to_string(),
+                ports: vec![PortMapping::new(8080, 80)],
+            }
+        );
+    }
+
+    #[test]
+    fn parses_flags_with_inline_values() {
+        let process =
+            parse("/usr/local/bin/kubectl port-forward --context=prod --namespace=shop api 9000")
+                .unwrap();
+
+        assert_eq!(process.context.as_deref(), Some("prod"));
+        assert_eq!(process.namespace.as_deref(), Some("shop"));
+        assert_eq!(process.kind, TargetKind::Pod);
+        assert_eq!(process.target_name, "api");
+        assert_eq!(process.ports, vec![PortMapping::new(9000, 9000)]);
+    }
+
+    #[test]
+    fn leaves_context_and_namespace_to_the_kubeconfig() {
+        let process = parse("kubectl port-forward pod/api 8080:80").unwrap();
+
+        assert_eq!(process.context, None);
+        assert_eq!(process.namespace, None);
+    }
+
+    #[test]
+    fn skips_values_of_other_flags() {
+        let process = parse(
+            "kubectl --kubeconfig /tmp/config port-forward --address 0.0.0.0 deployment.apps/web \
+             8080:80 8443:443 --pod-running-timeout 1m",
+        )
+        .unwrap();
+
+        assert_eq!(process.kind, TargetKind::Deployment);
+        assert_eq!(process.target_name, "web");
+        assert_eq!(
+            process.ports,
+            vec![PortMapping::new(8080, 80), PortMapping::new(8443, 443)]
+        );
+    }
+
+    #[test]
+    fn rejects_other_commands() {
+        assert_eq!(parse("kubectl logs -f api"), None);
+        assert_eq!(parse("kubectl port-forward job/backup 8080:80"), None);
+        assert_eq!(parse("oc port-forward api 8080:80"), None);
+        assert_eq!(parse("kubectl port-forward api"), None);
+    }
+
+    #[test]
+    fn rejects_random_local_ports() {
+        assert_eq!(parse("kubectl port-forward api :80"), None);
+    }
+
+    #[test]
+    fn rejects_flags_missing_their_value() {
+        assert_eq!(parse("kubectl port-forward api 8080:80 -n"), None);
+    }
+}
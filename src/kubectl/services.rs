@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::AppResult,
+    kubectl::{run_kubectl_command, with_context},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Service {
+    pub name: String,
+    pub service_type: String,
+    pub cluster_ip: String,
+    pub ports: Vec<ServicePort>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServicePort {
+    pub name: Option<String>,
+    pub port: u16,
+    pub protocol: String,
+}
+
+impl ServicePort {
+    /// Named ports read better than numbers, e.g. `http (80/TCP)`.
+    pub fn label(&self) -> String {
+        match &self.name {
+            Some(name) => format!("{name} ({}/{})", self.port, self.protocol),
+            None => format!("{}/{}", self.port, self.protocol),
+        }
+    }
+}
+
+pub async fn get_services(context: &str, namespace: &str) -> AppResult<Vec<Service>> {
+    let parsed: ApiResponse = run_kubectl_command(
+        "kubectl",
+        with_context(
+            context,
+            vec!["get", "services", "-n", namespace, "-o", "json"],
+        ),
+    )
+    .await?;
+
+    Ok(parsed
+        .items
+        .into_iter()
+        .map(|item| Service {
+            name: item.metadata.name,
+            service_type: item.spec.service_type,
+            cluster_ip: item.spec.cluster_ip.unwrap_or_default(),
+            ports: item
+                .spec
+                .ports
+                .into_iter()
+                .map(|port| ServicePort {
+                    name: port.name,
+                    port: port.port,
+                    protocol: port.protocol.unwrap_or_else(|| "TCP".to_string()),
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+#[derive(Deserialize)]
+struct ApiResponse {
+    items: Vec<Item>,
+}
+
+#[derive(Deserialize)]
+struct Item {
+    metadata: Metadata,
+    spec: Spec,
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Spec {
+    #[serde(rename = "type", default)]
+    service_type: String,
+    #[serde(rename = "clusterIP")]
+    cluster_ip: Option<String>,
+    #[serde(default)]
+    ports: Vec<Port>,
+}
+
+#[derive(Deserialize)]
+struct Port {
+    name: Option<String>,
+    port: u16,
+    protocol: Option<String>,
+}
@@ -0,0 +1,94 @@
+use std::{process::Stdio, sync::Arc};
+
+use anyhow::Context;
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    process::Command,
+    task::JoinHandle,
+};
+
+use crate::error::{AppError, AppResult};
+
+#[derive(Debug, Clone)]
+pub enum StreamEvent {
+    Stdout(String),
+    Stderr(String),
+    Exited(Option<i32>),
+}
+
+/// Handle to a long running kubectl process (`logs -f`, `get -w`, ...).
+/// The process is killed once the last clone of the handle is dropped.
+#[derive(Debug, Clone)]
+pub struct KubectlStream {
+    _task: Arc<AbortOnDrop>,
+}
+
+impl KubectlStream {
+    /// Ties a task that drives one or more kubectl processes to a handle, so that aborting the
+    /// task also kills every process it owns.
+    pub fn from_task(task: JoinHandle<()>) -> Self {
+        Self {
+            _task: Arc::new(AbortOnDrop(task)),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct AbortOnDrop(JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+pub fn stream_kubectl_command<F>(args: Vec<&str>, on_event: F) -> AppResult<KubectlStream>
+where
+    F: Fn(StreamEvent) + Send + 'static,
+{
+    stream_command("kubectl", args, on_event)
+}
+
+/// Runs any command like a kubectl stream, e.g. `tail -F` of a log file.
+pub fn stream_command<F>(command: &str, args: Vec<&str>, on_event: F) -> AppResult<KubectlStream>
+where
+    F: Fn(StreamEvent) + Send + 'static,
+{
+    let mut child = Command::new(command)
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to run command {} '{}'", command, args.join(" ")))
+        .map_err(AppError::FailedRunKubeCtlCommand)?;
+
+    let mut stdout = child.stdout.take().map(|out| BufReader::new(out).lines());
+    let mut stderr = child.stderr.take().map(|err| BufReader::new(err).lines());
+
+    let task = tokio::spawn(async move {
+        while stdout.is_some() || stderr.is_some() {
+            tokio::select! {
+                line = async { stdout.as_mut()?.next_line().await.ok().flatten() }, if stdout.is_some() => {
+                    match line {
+                        Some(line) => on_event(StreamEvent::Stdout(line)),
+                        None => stdout = None,
+                    }
+                }
+
+                line = async { stderr.as_mut()?.next_line().await.ok().flatten() }, if stderr.is_some() => {
+                    match line {
+                        Some(line) => on_event(StreamEvent::Stderr(line)),
+                        None => stderr = None,
+                    }
+                }
+            }
+        }
+
+        let exit_code = child.wait().await.ok().and_then(|status| status.code());
+        on_event(StreamEvent::Exited(exit_code));
+    });
+
+    Ok(KubectlStream::from_task(task))
+}
@@ -1,14 +1,43 @@
 mod app;
+mod cli;
 mod error;
 mod files;
 mod kubectl;
 
-use crate::{app::App, error::AppResult};
+use std::process::ExitCode;
+
+use crate::{
+    app::{App, headless},
+    cli::{Mode, USAGE},
+    error::AppResult,
+};
 
 #[tokio::main]
-async fn main() -> AppResult<()> {
+async fn main() -> AppResult<ExitCode> {
+    match cli::parse_args(std::env::args()) {
+        Ok(Mode::Tui) => {}
+        Ok(Mode::Help) => {
+            println!("{USAGE}");
+            return Ok(ExitCode::SUCCESS);
+        }
+        Ok(Mode::PortForwards(command)) => {
+            return Ok(match headless::run_port_forwards(command).await {
+                Ok(true) => ExitCode::SUCCESS,
+                Ok(false) => ExitCode::FAILURE,
+                Err(err) => {
+                    eprintln!("error: {err}");
+                    ExitCode::FAILURE
+                }
+            });
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            return Ok(ExitCode::from(2));
+        }
+    }
+
     let mut terminal = ratatui::init();
     let app_result = App::default().run(&mut terminal).await;
     ratatui::restore();
-    app_result
+    app_result.map(|_| ExitCode::SUCCESS)
 }